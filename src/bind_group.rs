@@ -2,33 +2,74 @@ use std::num::NonZero;
 
 use wgpu::{
     BindGroup, BindGroupLayout, BindingResource, Buffer, BufferAddress, BufferSize, Sampler,
-    TextureView,
+    Texture, TextureView,
 };
 
 use crate::{
-    PassContext, ResourceRead, ResourceRef, TransientBuffer, TransientResourceCreator,
-    TransientTexture, TransientTextureViewDescriptor,
+    PassContext, ResourceRead, ResourceReadWrite, ResourceRef, ResourceView, ResourceWrite,
+    TextureViewCache, TransientBuffer, TransientTexture, TransientTextureViewDescriptor,
 };
 
+pub type TransientBindGroupReadWriteBuffer = TransientBindGroupBuffer<ResourceReadWrite>;
+
+pub type TransientBindGroupWriteOnlyBuffer = TransientBindGroupBuffer<ResourceWrite>;
+
 #[derive(Clone, PartialEq, Eq)]
-pub struct TransientBindGroupBuffer {
-    pub buffer: ResourceRef<TransientBuffer, ResourceRead>,
+pub struct TransientBindGroupBuffer<ViewType = ResourceRead> {
+    pub buffer: ResourceRef<TransientBuffer, ViewType>,
     pub size: Option<NonZero<u64>>,
     pub offset: u64,
 }
 
+impl<ViewType: ResourceView> TransientBindGroupBuffer<ViewType> {
+    pub fn get_buffer_binding(&self, context: &PassContext<'_>) -> BufferBinding {
+        let buffer = context.resource_table().get_resource(&self.buffer);
+
+        BufferBinding {
+            buffer: buffer.resource.clone(),
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+}
+
+pub type TransientBindGroupReadWriteTextureView = TransientBindGroupTextureView<ResourceReadWrite>;
+
+pub type TransientBindGroupWriteOnlyTextureView = TransientBindGroupTextureView<ResourceWrite>;
+
 #[derive(Clone, PartialEq, Eq)]
-pub struct TransientBindGroupTextureView {
-    pub texture: ResourceRef<TransientTexture, ResourceRead>,
+pub struct TransientBindGroupTextureView<ViewType = ResourceRead> {
+    pub texture: ResourceRef<TransientTexture, ViewType>,
     pub texture_view_desc: TransientTextureViewDescriptor,
 }
 
+impl<ViewType: ResourceView> TransientBindGroupTextureView<ViewType> {
+    pub fn get_texture_view_binding(&self, context: &PassContext<'_>) -> TextureViewBinding {
+        let texture = context.resource_table().get_resource(&self.texture);
+
+        TextureViewBinding {
+            texture: texture.resource.clone(),
+            desc: self.texture_view_desc.clone(),
+        }
+    }
+
+    pub fn create_texture_view(&self, context: &PassContext<'_>) -> TextureView {
+        self.get_texture_view_binding(context).create_texture_view()
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum TransientBindGroupResource {
     Buffer(TransientBindGroupBuffer),
+    BufferArray(Vec<TransientBindGroupBuffer>),
+    ReadWriteStorageBuffer(TransientBindGroupReadWriteBuffer),
+    WriteOnlyStorageBuffer(TransientBindGroupWriteOnlyBuffer),
     Sampler(Sampler),
+    SamplerArray(Vec<Sampler>),
     TextureView(TransientBindGroupTextureView),
     TextureViewArray(Vec<TransientBindGroupTextureView>),
+    ReadWriteStorageTextureView(TransientBindGroupReadWriteTextureView),
+    WriteOnlyStorageTextureView(TransientBindGroupWriteOnlyTextureView),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -43,7 +84,7 @@ pub struct GpuBindGroupEntry {
     pub resource: GpuBindingResource,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BufferBinding {
     pub buffer: Buffer,
     pub offset: BufferAddress,
@@ -70,52 +111,196 @@ pub enum GpuBindingResource {
     TextureViewArray(Vec<TextureView>),
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TextureViewBinding {
+    pub texture: Texture,
+    pub desc: TransientTextureViewDescriptor,
+}
+
+impl TextureViewBinding {
+    pub fn create_texture_view(&self) -> TextureView {
+        self.texture.create_view(&self.desc.get_desc())
+    }
+
+    pub fn get_or_create_texture_view(
+        &self,
+        texture_view_cache: &mut TextureViewCache,
+    ) -> TextureView {
+        texture_view_cache.get_or_create_texture_view(&self.texture, &self.desc)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum BindGroupResourceKey {
+    Buffer(BufferBinding),
+    BufferArray(Vec<BufferBinding>),
+    Sampler(Sampler),
+    SamplerArray(Vec<Sampler>),
+    TextureView(TextureViewBinding),
+    TextureViewArray(Vec<TextureViewBinding>),
+}
+
+impl BindGroupResourceKey {
+    pub fn references_buffer(&self, buffer: &Buffer) -> bool {
+        match self {
+            BindGroupResourceKey::Buffer(binding) => binding.buffer == *buffer,
+            BindGroupResourceKey::BufferArray(bindings) => {
+                bindings.iter().any(|binding| binding.buffer == *buffer)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn references_texture(&self, texture: &Texture) -> bool {
+        match self {
+            BindGroupResourceKey::TextureView(binding) => binding.texture == *texture,
+            BindGroupResourceKey::TextureViewArray(bindings) => {
+                bindings.iter().any(|binding| binding.texture == *texture)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn create_gpu_binding_resource(&self) -> GpuBindingResource {
+        self.get_gpu_binding_resource(|binding| binding.create_texture_view())
+    }
+
+    pub fn get_or_create_gpu_binding_resource(
+        &self,
+        texture_view_cache: &mut TextureViewCache,
+    ) -> GpuBindingResource {
+        self.get_gpu_binding_resource(|binding| {
+            binding.get_or_create_texture_view(texture_view_cache)
+        })
+    }
+
+    fn get_gpu_binding_resource(
+        &self,
+        mut get_texture_view: impl FnMut(&TextureViewBinding) -> TextureView,
+    ) -> GpuBindingResource {
+        match self {
+            BindGroupResourceKey::Buffer(binding) => GpuBindingResource::Buffer(binding.clone()),
+            BindGroupResourceKey::BufferArray(bindings) => {
+                GpuBindingResource::BufferArray(bindings.clone())
+            }
+            BindGroupResourceKey::Sampler(sampler) => GpuBindingResource::Sampler(sampler.clone()),
+            BindGroupResourceKey::SamplerArray(samplers) => {
+                GpuBindingResource::SamplerArray(samplers.clone())
+            }
+            BindGroupResourceKey::TextureView(binding) => {
+                GpuBindingResource::TextureView(get_texture_view(binding))
+            }
+            BindGroupResourceKey::TextureViewArray(bindings) => {
+                GpuBindingResource::TextureViewArray(
+                    bindings.iter().map(get_texture_view).collect(),
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BindGroupEntryKey {
+    pub binding: u32,
+    pub resource: BindGroupResourceKey,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BindGroupKey {
+    pub layout: BindGroupLayout,
+    pub entries: Vec<BindGroupEntryKey>,
+}
+
+impl BindGroupKey {
+    pub fn references_buffer(&self, buffer: &Buffer) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.resource.references_buffer(buffer))
+    }
+
+    pub fn references_texture(&self, texture: &Texture) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.resource.references_texture(texture))
+    }
+
+    pub fn create_bind_group_descriptor(
+        &self,
+        label: Option<String>,
+        texture_view_cache: &mut TextureViewCache,
+    ) -> TransientBindGroupDescriptor {
+        TransientBindGroupDescriptor {
+            label,
+            layout: self.layout.clone(),
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| GpuBindGroupEntry {
+                    binding: entry.binding,
+                    resource: entry
+                        .resource
+                        .get_or_create_gpu_binding_resource(texture_view_cache),
+                })
+                .collect(),
+        }
+    }
+}
+
 impl TransientBindGroupEntry {
-    pub fn get_gpu_bind_group_entry(&self, context: &PassContext<'_>) -> GpuBindGroupEntry {
-        match &self.resource {
+    pub fn get_bind_group_entry_key(&self, context: &PassContext<'_>) -> BindGroupEntryKey {
+        let resource = match &self.resource {
             TransientBindGroupResource::Buffer(binding) => {
-                let buffer = context.resource_table().get_resource(&binding.buffer);
-
-                GpuBindGroupEntry {
-                    binding: self.binding,
-                    resource: GpuBindingResource::Buffer(BufferBinding {
-                        buffer: buffer.resource.clone(),
-                        offset: binding.offset,
-                        size: binding.size,
-                    }),
-                }
+                BindGroupResourceKey::Buffer(binding.get_buffer_binding(context))
+            }
+            TransientBindGroupResource::BufferArray(bindings) => BindGroupResourceKey::BufferArray(
+                bindings
+                    .iter()
+                    .map(|binding| binding.get_buffer_binding(context))
+                    .collect(),
+            ),
+            TransientBindGroupResource::ReadWriteStorageBuffer(binding) => {
+                BindGroupResourceKey::Buffer(binding.get_buffer_binding(context))
+            }
+            TransientBindGroupResource::WriteOnlyStorageBuffer(binding) => {
+                BindGroupResourceKey::Buffer(binding.get_buffer_binding(context))
+            }
+            TransientBindGroupResource::Sampler(sampler) => {
+                BindGroupResourceKey::Sampler(sampler.clone())
+            }
+            TransientBindGroupResource::SamplerArray(samplers) => {
+                BindGroupResourceKey::SamplerArray(samplers.clone())
             }
-            TransientBindGroupResource::Sampler(sampler) => GpuBindGroupEntry {
-                binding: self.binding,
-                resource: GpuBindingResource::Sampler(sampler.clone()),
-            },
             TransientBindGroupResource::TextureView(binding) => {
-                let texture = context.resource_table().get_resource(&binding.texture);
-                let texture_view = texture
-                    .resource
-                    .create_view(&binding.texture_view_desc.get_desc());
-
-                GpuBindGroupEntry {
-                    binding: self.binding,
-                    resource: GpuBindingResource::TextureView(texture_view),
-                }
+                BindGroupResourceKey::TextureView(binding.get_texture_view_binding(context))
             }
             TransientBindGroupResource::TextureViewArray(bindings) => {
-                let bindings = bindings
-                    .iter()
-                    .map(|binding| {
-                        let texture = context.resource_table().get_resource(&binding.texture);
-                        texture
-                            .resource
-                            .create_view(&binding.texture_view_desc.get_desc())
-                    })
-                    .collect();
-
-                GpuBindGroupEntry {
-                    binding: self.binding,
-                    resource: GpuBindingResource::TextureViewArray(bindings),
-                }
+                BindGroupResourceKey::TextureViewArray(
+                    bindings
+                        .iter()
+                        .map(|binding| binding.get_texture_view_binding(context))
+                        .collect(),
+                )
+            }
+            TransientBindGroupResource::ReadWriteStorageTextureView(binding) => {
+                BindGroupResourceKey::TextureView(binding.get_texture_view_binding(context))
+            }
+            TransientBindGroupResource::WriteOnlyStorageTextureView(binding) => {
+                BindGroupResourceKey::TextureView(binding.get_texture_view_binding(context))
             }
+        };
+
+        BindGroupEntryKey {
+            binding: self.binding,
+            resource,
+        }
+    }
+
+    pub fn get_gpu_bind_group_entry(&self, context: &PassContext<'_>) -> GpuBindGroupEntry {
+        let key = self.get_bind_group_entry_key(context);
+
+        GpuBindGroupEntry {
+            binding: key.binding,
+            resource: key.resource.create_gpu_binding_resource(),
         }
     }
 }
@@ -157,6 +342,17 @@ pub struct TransientBindGroup {
 }
 
 impl TransientBindGroup {
+    pub fn get_bind_group_key(&self, context: &PassContext<'_>) -> BindGroupKey {
+        BindGroupKey {
+            layout: self.layout.clone(),
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| entry.get_bind_group_entry_key(context))
+                .collect(),
+        }
+    }
+
     pub fn create_bind_group(&self, context: &PassContext<'_>) -> BindGroup {
         let entries = self
             .entries
@@ -170,6 +366,6 @@ impl TransientBindGroup {
             entries,
         };
 
-        TransientResourceCreator::create_bind_group(context.device(), &desc)
+        context.resource_creator().create_bind_group(&desc)
     }
 }
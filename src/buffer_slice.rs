@@ -0,0 +1,29 @@
+use std::num::NonZero;
+
+use crate::{BufferRange, ResourceRef, TransientBindGroupBuffer, TransientBuffer};
+
+#[derive(Clone)]
+pub struct TransientBufferSlice<ViewType> {
+    pub buffer: ResourceRef<TransientBuffer, ViewType>,
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl<ViewType> TransientBufferSlice<ViewType> {
+    pub fn range(&self) -> BufferRange {
+        BufferRange {
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+}
+
+impl<ViewType> From<TransientBufferSlice<ViewType>> for TransientBindGroupBuffer<ViewType> {
+    fn from(value: TransientBufferSlice<ViewType>) -> Self {
+        TransientBindGroupBuffer {
+            buffer: value.buffer,
+            size: NonZero::new(value.size),
+            offset: value.offset,
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::{fmt, sync::mpsc};
+
+use wgpu::{
+    BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, CommandEncoderDescriptor, Device,
+    MapMode, Origin3d, PollType, TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo,
+    TextureAspect, TextureUsages,
+};
+
+use crate::{
+    ArcAnyTransientResource, FrameCapture, FrameGraphContext, TransientBuffer, TransientTexture,
+    TransientTextureDescriptor,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkippedContentsReason {
+    MissingCopySrc,
+    Multisampled,
+    UnsupportedFormat,
+    TextureView,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedContents {
+    pub resource: String,
+    pub reason: SkippedContentsReason,
+}
+
+fn skipped_reason(imported: &ArcAnyTransientResource) -> Option<SkippedContentsReason> {
+    match imported {
+        ArcAnyTransientResource::Buffer(buffer) => {
+            (!buffer.desc.usage.contains(BufferUsages::COPY_SRC))
+                .then_some(SkippedContentsReason::MissingCopySrc)
+        }
+        ArcAnyTransientResource::Texture(texture) => {
+            if !texture.desc.usage.contains(TextureUsages::COPY_SRC) {
+                Some(SkippedContentsReason::MissingCopySrc)
+            } else if texture.desc.sample_count > 1 {
+                Some(SkippedContentsReason::Multisampled)
+            } else if texture.desc.format.block_copy_size(None).is_none() {
+                Some(SkippedContentsReason::UnsupportedFormat)
+            } else {
+                None
+            }
+        }
+        ArcAnyTransientResource::TextureView(_) => Some(SkippedContentsReason::TextureView),
+    }
+}
+
+pub(crate) struct TextureReadbackLayout {
+    pub unpadded_bytes_per_row: u32,
+    padded_bytes_per_row: u32,
+    pub rows: u32,
+    layers: u32,
+}
+
+impl TextureReadbackLayout {
+    pub fn new(desc: &TransientTextureDescriptor, mip_level: u32) -> Option<Self> {
+        let format = desc.format;
+        let block_size = format.block_copy_size(None)?;
+        let (block_width, block_height) = format.block_dimensions();
+        let size = desc.size.mip_level_size(mip_level, desc.dimension);
+
+        let unpadded_bytes_per_row = size.width.div_ceil(block_width) * block_size;
+        let padded_bytes_per_row =
+            unpadded_bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        Some(Self {
+            unpadded_bytes_per_row,
+            padded_bytes_per_row,
+            rows: size.height.div_ceil(block_height),
+            layers: size.depth_or_array_layers,
+        })
+    }
+
+    fn size(&self) -> u64 {
+        self.padded_bytes_per_row as u64 * self.rows as u64 * self.layers as u64
+    }
+
+    pub fn unpadded_size(&self) -> usize {
+        self.unpadded_bytes_per_row as usize * self.rows as usize * self.layers as usize
+    }
+
+    fn unpad(&self, bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .chunks_exact(self.padded_bytes_per_row as usize)
+            .flat_map(|row| &row[..self.unpadded_bytes_per_row as usize])
+            .copied()
+            .collect()
+    }
+}
+
+struct PendingContents {
+    resource: usize,
+    readback: wgpu::Buffer,
+    layout: Option<TextureReadbackLayout>,
+}
+
+#[derive(Default)]
+pub(crate) struct CapturedContents {
+    imports: Vec<(usize, ArcAnyTransientResource)>,
+    pending: Vec<PendingContents>,
+}
+
+impl fmt::Debug for CapturedContents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapturedContents")
+            .field("imports", &self.imports.len())
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl CapturedContents {
+    pub fn push_import(
+        &mut self,
+        resource: usize,
+        imported: ArcAnyTransientResource,
+    ) -> Result<(), SkippedContentsReason> {
+        if let Some(reason) = skipped_reason(&imported) {
+            return Err(reason);
+        }
+
+        self.imports.push((resource, imported));
+        Ok(())
+    }
+
+    fn readback_buffer(context: &FrameGraphContext, size: u64) -> wgpu::Buffer {
+        context.device().create_buffer(&BufferDescriptor {
+            label: Some("frame_capture_readback"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn copy_buffer(
+        &mut self,
+        resource: usize,
+        buffer: &TransientBuffer,
+        command_encoder: &mut wgpu::CommandEncoder,
+        context: &FrameGraphContext,
+    ) {
+        if buffer.desc.size == 0 {
+            return;
+        }
+
+        let readback = Self::readback_buffer(context, buffer.desc.size);
+        command_encoder.copy_buffer_to_buffer(&buffer.resource, 0, &readback, 0, buffer.desc.size);
+
+        self.pending.push(PendingContents {
+            resource,
+            readback,
+            layout: None,
+        });
+    }
+
+    fn copy_texture(
+        &mut self,
+        resource: usize,
+        texture: &TransientTexture,
+        command_encoder: &mut wgpu::CommandEncoder,
+        context: &FrameGraphContext,
+    ) {
+        for mip_level in 0..texture.desc.mip_level_count {
+            let Some(layout) = TextureReadbackLayout::new(&texture.desc, mip_level) else {
+                return;
+            };
+
+            let readback = Self::readback_buffer(context, layout.size());
+            command_encoder.copy_texture_to_buffer(
+                TexelCopyTextureInfo {
+                    texture: &texture.resource,
+                    mip_level,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                TexelCopyBufferInfo {
+                    buffer: &readback,
+                    layout: TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(layout.padded_bytes_per_row),
+                        rows_per_image: Some(layout.rows),
+                    },
+                },
+                texture
+                    .desc
+                    .size
+                    .mip_level_size(mip_level, texture.desc.dimension),
+            );
+
+            self.pending.push(PendingContents {
+                resource,
+                readback,
+                layout: Some(layout),
+            });
+        }
+    }
+
+    pub fn encode_readback(&mut self, context: &mut FrameGraphContext) {
+        if self.imports.is_empty() {
+            return;
+        }
+
+        let mut command_encoder =
+            context
+                .device()
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some("frame_capture_readback"),
+                });
+
+        for (resource, imported) in std::mem::take(&mut self.imports) {
+            match imported {
+                ArcAnyTransientResource::Buffer(buffer) => {
+                    self.copy_buffer(resource, &buffer, &mut command_encoder, context)
+                }
+                ArcAnyTransientResource::Texture(texture) => {
+                    self.copy_texture(resource, &texture, &mut command_encoder, context)
+                }
+                ArcAnyTransientResource::TextureView(_) => {}
+            }
+        }
+
+        context.add_command_buffer(command_encoder.finish());
+    }
+}
+
+impl FrameCapture {
+    pub fn has_pending_contents(&self) -> bool {
+        !self.contents.pending.is_empty()
+    }
+
+    pub fn resolve_contents(&mut self, device: &Device) -> bool {
+        let pending = std::mem::take(&mut self.contents.pending);
+        if pending.is_empty() {
+            return false;
+        }
+
+        let receivers = pending
+            .iter()
+            .map(|contents| {
+                let (sender, receiver) = mpsc::channel();
+                contents
+                    .readback
+                    .slice(..)
+                    .map_async(MapMode::Read, move |result| {
+                        let _ = sender.send(result);
+                    });
+                receiver
+            })
+            .collect::<Vec<_>>();
+
+        if device.poll(PollType::wait_indefinitely()).is_err() {
+            return false;
+        }
+
+        let mut failed = vec![];
+        for (contents, receiver) in pending.into_iter().zip(receivers) {
+            if !matches!(receiver.try_recv(), Ok(Ok(()))) {
+                failed.push(contents.resource);
+                continue;
+            }
+
+            let bytes = {
+                let mapped = contents.readback.slice(..).get_mapped_range();
+                match &contents.layout {
+                    Some(layout) => layout.unpad(&mapped),
+                    None => mapped.to_vec(),
+                }
+            };
+            contents.readback.unmap();
+
+            self.resources[contents.resource]
+                .contents
+                .get_or_insert_default()
+                .extend_from_slice(&bytes);
+        }
+
+        for resource in failed.iter() {
+            self.resources[*resource].contents = None;
+        }
+
+        failed.is_empty()
+    }
+}
@@ -0,0 +1,908 @@
+mod contents;
+mod replay;
+
+use contents::CapturedContents;
+pub use contents::{SkippedContents, SkippedContentsReason};
+pub use replay::*;
+
+use std::num::NonZero;
+
+use wgpu::{BindGroupLayout, Color, IndexFormat, Operations, RenderPipeline, Sampler, TextureView};
+
+use crate::{
+    AnyTransientResourceDescriptor, DrawIndexedParameter, DrawParameter, FrameGraph,
+    RawResourceHandle, ResourceKindSnapshot, ResourceRangeWrite, ResourceRef, TextureViewEdge,
+    TransientBindGroup, TransientBindGroupBuffer, TransientBindGroupResource,
+    TransientBindGroupTextureView, TransientRenderBundleDescriptor, TransientResource,
+    TransientTextureView, TransientTextureViewDescriptor, VirtualResource,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedResourceRef {
+    pub resource: usize,
+    pub version: u32,
+}
+
+impl CapturedResourceRef {
+    pub fn new<ResourceType: TransientResource, ViewType>(
+        resource_ref: &ResourceRef<ResourceType, ViewType>,
+    ) -> Self {
+        Self::from_raw(&resource_ref.raw)
+    }
+
+    pub fn from_raw(raw: &RawResourceHandle) -> Self {
+        Self {
+            resource: raw.index.index,
+            version: raw.version,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CapturedTextureView {
+    Read {
+        texture: CapturedResourceRef,
+        desc: TransientTextureViewDescriptor,
+    },
+    Write {
+        texture: CapturedResourceRef,
+        desc: TransientTextureViewDescriptor,
+    },
+    ImportedRead(CapturedResourceRef),
+    ImportedWrite(CapturedResourceRef),
+    Owned(usize),
+}
+
+impl CapturedTextureView {
+    pub fn new(edge: &TextureViewEdge, objects: &mut CaptureObjects) -> Self {
+        fn view<ViewType>(view: &TransientTextureView<ViewType>) -> CapturedResourceRef {
+            CapturedResourceRef::new(&view.texture)
+        }
+
+        match edge {
+            TextureViewEdge::Read(texture_view) => CapturedTextureView::Read {
+                texture: view(texture_view),
+                desc: texture_view.desc.clone(),
+            },
+            TextureViewEdge::Write(texture_view) => CapturedTextureView::Write {
+                texture: view(texture_view),
+                desc: texture_view.desc.clone(),
+            },
+            TextureViewEdge::ImportedRead(texture_view) => {
+                CapturedTextureView::ImportedRead(CapturedResourceRef::new(texture_view))
+            }
+            TextureViewEdge::ImportedWrite(texture_view) => {
+                CapturedTextureView::ImportedWrite(CapturedResourceRef::new(texture_view))
+            }
+            TextureViewEdge::Owned(texture_view) => {
+                CapturedTextureView::Owned(objects.texture_view(texture_view))
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedColorAttachment {
+    pub view: CapturedTextureView,
+    pub depth_slice: Option<u32>,
+    pub resolve_target: Option<CapturedTextureView>,
+    pub ops: Operations<Color>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedDepthStencilAttachment {
+    pub view: CapturedTextureView,
+    pub depth_ops: Option<Operations<f32>>,
+    pub stencil_ops: Option<Operations<u32>>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedBindGroupBuffer {
+    pub buffer: CapturedResourceRef,
+    pub offset: u64,
+    pub size: Option<NonZero<u64>>,
+}
+
+impl CapturedBindGroupBuffer {
+    fn new<ViewType>(binding: &TransientBindGroupBuffer<ViewType>) -> Self {
+        Self {
+            buffer: CapturedResourceRef::new(&binding.buffer),
+            offset: binding.offset,
+            size: binding.size,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedBindGroupTextureView {
+    pub texture: CapturedResourceRef,
+    pub desc: TransientTextureViewDescriptor,
+}
+
+impl CapturedBindGroupTextureView {
+    fn new<ViewType>(binding: &TransientBindGroupTextureView<ViewType>) -> Self {
+        Self {
+            texture: CapturedResourceRef::new(&binding.texture),
+            desc: binding.texture_view_desc.clone(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CapturedBindGroupResource {
+    Buffer(CapturedBindGroupBuffer),
+    BufferArray(Vec<CapturedBindGroupBuffer>),
+    ReadWriteStorageBuffer(CapturedBindGroupBuffer),
+    WriteOnlyStorageBuffer(CapturedBindGroupBuffer),
+    Sampler(usize),
+    SamplerArray(Vec<usize>),
+    TextureView(CapturedBindGroupTextureView),
+    TextureViewArray(Vec<CapturedBindGroupTextureView>),
+    ReadWriteStorageTextureView(CapturedBindGroupTextureView),
+    WriteOnlyStorageTextureView(CapturedBindGroupTextureView),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedBindGroupEntry {
+    pub binding: u32,
+    pub resource: CapturedBindGroupResource,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedBindGroup {
+    pub label: Option<String>,
+    pub layout: usize,
+    pub entries: Vec<CapturedBindGroupEntry>,
+}
+
+impl CapturedBindGroup {
+    pub fn new(bind_group: &TransientBindGroup, objects: &mut CaptureObjects) -> Self {
+        let entries = bind_group
+            .entries
+            .iter()
+            .map(|entry| {
+                let resource = match &entry.resource {
+                    TransientBindGroupResource::Buffer(binding) => {
+                        CapturedBindGroupResource::Buffer(CapturedBindGroupBuffer::new(binding))
+                    }
+                    TransientBindGroupResource::BufferArray(bindings) => {
+                        CapturedBindGroupResource::BufferArray(
+                            bindings.iter().map(CapturedBindGroupBuffer::new).collect(),
+                        )
+                    }
+                    TransientBindGroupResource::ReadWriteStorageBuffer(binding) => {
+                        CapturedBindGroupResource::ReadWriteStorageBuffer(
+                            CapturedBindGroupBuffer::new(binding),
+                        )
+                    }
+                    TransientBindGroupResource::WriteOnlyStorageBuffer(binding) => {
+                        CapturedBindGroupResource::WriteOnlyStorageBuffer(
+                            CapturedBindGroupBuffer::new(binding),
+                        )
+                    }
+                    TransientBindGroupResource::Sampler(sampler) => {
+                        CapturedBindGroupResource::Sampler(objects.sampler(sampler))
+                    }
+                    TransientBindGroupResource::SamplerArray(samplers) => {
+                        CapturedBindGroupResource::SamplerArray(
+                            samplers
+                                .iter()
+                                .map(|sampler| objects.sampler(sampler))
+                                .collect(),
+                        )
+                    }
+                    TransientBindGroupResource::TextureView(binding) => {
+                        CapturedBindGroupResource::TextureView(CapturedBindGroupTextureView::new(
+                            binding,
+                        ))
+                    }
+                    TransientBindGroupResource::TextureViewArray(bindings) => {
+                        CapturedBindGroupResource::TextureViewArray(
+                            bindings
+                                .iter()
+                                .map(CapturedBindGroupTextureView::new)
+                                .collect(),
+                        )
+                    }
+                    TransientBindGroupResource::ReadWriteStorageTextureView(binding) => {
+                        CapturedBindGroupResource::ReadWriteStorageTextureView(
+                            CapturedBindGroupTextureView::new(binding),
+                        )
+                    }
+                    TransientBindGroupResource::WriteOnlyStorageTextureView(binding) => {
+                        CapturedBindGroupResource::WriteOnlyStorageTextureView(
+                            CapturedBindGroupTextureView::new(binding),
+                        )
+                    }
+                };
+
+                CapturedBindGroupEntry {
+                    binding: entry.binding,
+                    resource,
+                }
+            })
+            .collect();
+
+        Self {
+            label: bind_group.label.clone(),
+            layout: objects.bind_group_layout(&bind_group.layout),
+            entries,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CapturedRenderPassCommand {
+    SetBindGroup {
+        index: u32,
+        bind_group: CapturedBindGroup,
+        offsets: Vec<u32>,
+    },
+    SetRenderPipeline(usize),
+    SetVertexBuffer {
+        slot: u32,
+        buffer: CapturedResourceRef,
+        offset: u64,
+        size: u64,
+    },
+    SetIndexBuffer {
+        buffer: CapturedResourceRef,
+        index_format: IndexFormat,
+        offset: u64,
+        size: u64,
+    },
+    Draw(DrawParameter),
+    DrawIndexed(DrawIndexedParameter),
+    ExecuteBundle {
+        desc: TransientRenderBundleDescriptor,
+        commands: Vec<CapturedRenderPassCommand>,
+    },
+    Unsupported(String),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedRenderPass {
+    pub label: Option<String>,
+    pub color_attachments: Vec<Option<CapturedColorAttachment>>,
+    pub depth_stencil_attachment: Option<CapturedDepthStencilAttachment>,
+    pub commands: Vec<CapturedRenderPassCommand>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CapturedPassCommand {
+    RenderPass(CapturedRenderPass),
+    Unsupported(String),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedPass {
+    pub name: String,
+    pub label: Option<String>,
+    pub dependencies: Vec<String>,
+    pub reads: Vec<CapturedResourceRef>,
+    pub writes: Vec<CapturedResourceRef>,
+    pub commands: Vec<CapturedPassCommand>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedResource {
+    pub name: String,
+    pub kind: ResourceKindSnapshot,
+    pub desc: AnyTransientResourceDescriptor,
+    pub output: bool,
+    pub exported: bool,
+    pub range_writes: Vec<ResourceRangeWrite>,
+    pub contents: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CaptureObjects {
+    pub render_pipelines: Vec<RenderPipeline>,
+    pub bind_group_layouts: Vec<BindGroupLayout>,
+    pub samplers: Vec<Sampler>,
+    pub texture_views: Vec<TextureView>,
+}
+
+fn intern<T: Clone + PartialEq>(objects: &mut Vec<T>, object: &T) -> usize {
+    if let Some(index) = objects.iter().position(|value| value == object) {
+        return index;
+    }
+
+    objects.push(object.clone());
+    objects.len() - 1
+}
+
+pub trait CaptureObjectResolver {
+    fn render_pipeline(&self, index: usize) -> Option<RenderPipeline>;
+    fn bind_group_layout(&self, index: usize) -> Option<BindGroupLayout>;
+    fn sampler(&self, index: usize) -> Option<Sampler>;
+    fn texture_view(&self, index: usize) -> Option<TextureView>;
+}
+
+impl CaptureObjects {
+    pub fn is_empty(&self) -> bool {
+        self.render_pipelines.is_empty()
+            && self.bind_group_layouts.is_empty()
+            && self.samplers.is_empty()
+            && self.texture_views.is_empty()
+    }
+
+    pub fn render_pipeline(&mut self, render_pipeline: &RenderPipeline) -> usize {
+        intern(&mut self.render_pipelines, render_pipeline)
+    }
+
+    pub fn bind_group_layout(&mut self, bind_group_layout: &BindGroupLayout) -> usize {
+        intern(&mut self.bind_group_layouts, bind_group_layout)
+    }
+
+    pub fn sampler(&mut self, sampler: &Sampler) -> usize {
+        intern(&mut self.samplers, sampler)
+    }
+
+    pub fn texture_view(&mut self, texture_view: &TextureView) -> usize {
+        intern(&mut self.texture_views, texture_view)
+    }
+}
+
+impl CaptureObjectResolver for CaptureObjects {
+    fn render_pipeline(&self, index: usize) -> Option<RenderPipeline> {
+        self.render_pipelines.get(index).cloned()
+    }
+
+    fn bind_group_layout(&self, index: usize) -> Option<BindGroupLayout> {
+        self.bind_group_layouts.get(index).cloned()
+    }
+
+    fn sampler(&self, index: usize) -> Option<Sampler> {
+        self.samplers.get(index).cloned()
+    }
+
+    fn texture_view(&self, index: usize) -> Option<TextureView> {
+        self.texture_views.get(index).cloned()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default)]
+pub struct FrameCapture {
+    pub resources: Vec<CapturedResource>,
+    pub passes: Vec<CapturedPass>,
+    pub skipped_contents: Vec<SkippedContents>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub objects: CaptureObjects,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) contents: CapturedContents,
+}
+
+impl FrameCapture {
+    pub(crate) fn new(frame_graph: &FrameGraph, capture_imported_contents: bool) -> Self {
+        let mut capture = FrameCapture::default();
+
+        for resource_node in frame_graph.resource_nodes.iter() {
+            let (kind, desc) = match &resource_node.resource {
+                VirtualResource::Setuped(desc) => (ResourceKindSnapshot::Transient, desc.clone()),
+                VirtualResource::Imported(resource) => {
+                    if capture_imported_contents
+                        && let Err(reason) = capture
+                            .contents
+                            .push_import(resource_node.index.index, resource.clone())
+                    {
+                        capture.skipped_contents.push(SkippedContents {
+                            resource: resource_node.name.clone(),
+                            reason,
+                        });
+                    }
+
+                    (ResourceKindSnapshot::Imported, resource.get_desc())
+                }
+                VirtualResource::History(history) => (
+                    ResourceKindSnapshot::History {
+                        key: history.key.clone(),
+                        slot: history.slot,
+                    },
+                    history.desc.clone(),
+                ),
+            };
+
+            capture.resources.push(CapturedResource {
+                name: resource_node.name.clone(),
+                kind,
+                desc,
+                output: resource_node.output,
+                exported: resource_node.exported,
+                range_writes: resource_node.range_writes.clone(),
+                contents: (!resource_node.contents.is_empty())
+                    .then(|| resource_node.contents.clone()),
+            });
+        }
+
+        for handle in frame_graph.execution_order.iter() {
+            let pass_node = frame_graph.get_pass_node(handle);
+            let (label, commands) = match &pass_node.pass {
+                Some(pass) => (pass.label.clone(), pass.capture(&mut capture.objects)),
+                None => (None, vec![]),
+            };
+
+            capture.passes.push(CapturedPass {
+                name: pass_node.name.clone(),
+                label,
+                dependencies: pass_node.dependencies.clone(),
+                reads: pass_node
+                    .reads
+                    .iter()
+                    .map(CapturedResourceRef::from_raw)
+                    .collect(),
+                writes: pass_node
+                    .writes
+                    .iter()
+                    .map(CapturedResourceRef::from_raw)
+                    .collect(),
+                commands,
+            });
+        }
+
+        capture
+    }
+
+    pub fn get_pass(&self, name: &str) -> Option<&CapturedPass> {
+        self.passes.iter().find(|pass| pass.name == name)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn write_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn read_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Ok(Self::from_json(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{
+        BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
+        BufferDescriptor, BufferUsages, Extent3d, Operations, ShaderStages, TextureDescriptor,
+        TextureDimension, TextureFormat, TextureUsages,
+    };
+
+    use crate::{
+        CapturedPassCommand, CapturedRenderPass, CapturedRenderPassCommand, CapturedResourceRef,
+        CapturedTextureView, DrawParameter, FrameCapture, FrameGraph, FrameGraphContext,
+        PassCommand, PassContext, PassNodeBuilderExt, PipelineContainer, RenderPass, RenderPassExt,
+        ReplayError, SkippedContents, SkippedContentsReason, TextureViewEdge, TransientBindGroup,
+        TransientBindGroupBuffer, TransientBindGroupEntry, TransientBindGroupResource,
+        TransientBuffer, TransientBufferDescriptor, TransientRenderPassColorAttachment,
+        TransientResourceCache, TransientTextureDescriptor, TransientTextureView,
+    };
+
+    fn build_frame(frame_graph: &mut FrameGraph) {
+        let vertices = frame_graph.create(
+            "vertices",
+            TransientBufferDescriptor {
+                label: None,
+                size: 36,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        let color = frame_graph.create(
+            "color",
+            TransientTextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                view_formats: vec![],
+            },
+        );
+        frame_graph.mark_output(&color);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("upload");
+            builder.write(vertices.clone());
+        }
+
+        let vertices = frame_graph
+            .get_resource_node(&vertices.raw.index)
+            .get_handle();
+
+        let mut builder = frame_graph.create_pass_buidlder("draw");
+        let vertices = builder.read::<TransientBuffer>(vertices);
+        let color = builder.write(color);
+
+        let mut render_pass_builder = builder.create_render_pass_builder("draw_pass");
+        render_pass_builder
+            .add_color_attachment(TransientRenderPassColorAttachment {
+                view: TextureViewEdge::Write(TransientTextureView {
+                    texture: color,
+                    desc: Default::default(),
+                }),
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })
+            .set_vertex_buffer(0, &vertices, 0, 36)
+            .draw(0..3, 0..1);
+    }
+
+    fn compiled_capture(frame_graph: &mut FrameGraph) -> FrameCapture {
+        frame_graph.compile().unwrap();
+        frame_graph
+            .compiled_frame_graph
+            .as_mut()
+            .unwrap()
+            .take_frame_capture()
+            .unwrap()
+    }
+
+    #[test]
+    fn frame_capture_records_render_pass_commands() {
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+        build_frame(&mut frame_graph);
+
+        let capture = compiled_capture(&mut frame_graph);
+
+        assert_eq!(capture.resources.len(), 2);
+        assert!(capture.get_pass("upload").unwrap().commands.is_empty());
+        assert!(capture.objects.is_empty());
+
+        let draw = capture.get_pass("draw").unwrap();
+        assert_eq!(
+            draw.commands,
+            vec![CapturedPassCommand::RenderPass(CapturedRenderPass {
+                label: Some("draw_pass".to_string()),
+                color_attachments: vec![Some(super::CapturedColorAttachment {
+                    view: CapturedTextureView::Write {
+                        texture: CapturedResourceRef {
+                            resource: 1,
+                            version: 1,
+                        },
+                        desc: Default::default(),
+                    },
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                commands: vec![
+                    CapturedRenderPassCommand::SetVertexBuffer {
+                        slot: 0,
+                        buffer: CapturedResourceRef {
+                            resource: 0,
+                            version: 1,
+                        },
+                        offset: 0,
+                        size: 36,
+                    },
+                    CapturedRenderPassCommand::Draw(DrawParameter {
+                        vertices: 0..3,
+                        instances: 0..1,
+                    }),
+                ],
+            })]
+        );
+    }
+
+    #[test]
+    fn frame_capture_replays_into_equivalent_frame_graph() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+        build_frame(&mut frame_graph);
+        let capture = compiled_capture(&mut frame_graph);
+
+        let mut replayed = FrameGraph::default();
+        replayed.set_frame_capture(true);
+        capture.replay(&mut replayed, &device, &queue).unwrap();
+        let recaptured = compiled_capture(&mut replayed);
+
+        assert_eq!(replayed.snapshot(), frame_graph.snapshot());
+        assert_eq!(recaptured.resources, capture.resources);
+        assert_eq!(recaptured.passes, capture.passes);
+    }
+
+    #[test]
+    fn frame_capture_replay_resolves_objects_through_the_resolver() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+
+        let constants = frame_graph.create(
+            "constants",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            },
+        );
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("draw");
+            let constants = builder.read(constants);
+            builder.write(output);
+
+            let mut render_pass = RenderPass::default();
+            render_pass.set_bind_group(
+                0,
+                &TransientBindGroup {
+                    label: None,
+                    layout,
+                    entries: vec![TransientBindGroupEntry {
+                        binding: 0,
+                        resource: TransientBindGroupResource::Buffer(TransientBindGroupBuffer {
+                            buffer: constants,
+                            size: None,
+                            offset: 0,
+                        }),
+                    }],
+                },
+                &[],
+            );
+            builder.push(render_pass);
+        }
+
+        let mut capture = compiled_capture(&mut frame_graph);
+        let objects = std::mem::take(&mut capture.objects);
+
+        assert_eq!(
+            capture
+                .replay(&mut FrameGraph::default(), &device, &queue)
+                .unwrap_err(),
+            ReplayError::MissingObject {
+                kind: "bind group layout",
+                index: 0,
+            }
+        );
+
+        let mut replayed = FrameGraph::default();
+        capture
+            .replay_with(&mut replayed, &device, &queue, &objects)
+            .unwrap();
+        replayed.compile().unwrap();
+        assert_eq!(replayed.snapshot(), frame_graph.snapshot());
+    }
+
+    struct Readback;
+
+    impl PassCommand for Readback {
+        fn execute(&self, _context: &mut PassContext) {}
+    }
+
+    #[test]
+    fn frame_capture_replay_rejects_unsupported_commands() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("readback");
+            builder.write(output);
+            builder.push(Readback);
+        }
+
+        let capture = compiled_capture(&mut frame_graph);
+
+        let error = capture
+            .replay(&mut FrameGraph::default(), &device, &queue)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ReplayError::UnsupportedCommand { ref pass, ref command }
+                if pass == "readback" && command.ends_with("Readback")
+        ));
+    }
+
+    #[test]
+    fn frame_capture_reads_back_imported_contents() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+        frame_graph.set_capture_imported_contents(true);
+
+        let constants = frame_graph.import_buffer(
+            "constants",
+            device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+        );
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+        frame_graph.import_texture(
+            "environment",
+            device.create_texture(&TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 2,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_SRC,
+                view_formats: &[],
+            }),
+        );
+        frame_graph.import_buffer(
+            "scratch",
+            device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            }),
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("consume");
+            builder.read::<TransientBuffer>(constants);
+            builder.write(output);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        queue.submit(context.finish(&queue));
+
+        let mut capture = frame_graph.take_frame_capture().unwrap();
+        assert!(capture.has_pending_contents());
+        assert!(capture.resolve_contents(&device));
+        assert_eq!(capture.resources[0].contents.as_ref().unwrap().len(), 64);
+        assert_eq!(capture.resources[1].contents, None);
+        assert_eq!(
+            capture.resources[2].contents.as_ref().unwrap().len(),
+            64 + 16
+        );
+        assert_eq!(capture.resources[3].contents, None);
+        assert_eq!(
+            capture.skipped_contents,
+            vec![SkippedContents {
+                resource: "scratch".to_string(),
+                reason: SkippedContentsReason::MissingCopySrc,
+            }]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use wgpu::BufferUsages;
+
+    use crate::{
+        FrameCapture, FrameGraph, PassNodeBuilderExt, RenderPass, RenderPassExt,
+        TransientBufferDescriptor,
+    };
+
+    #[test]
+    fn frame_capture_roundtrips_through_json() {
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_frame_capture(true);
+
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: Some("output".to_string()),
+                size: 16,
+                usage: BufferUsages::VERTEX,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("draw");
+            builder.write(output);
+            let mut render_pass = RenderPass::default();
+            render_pass.set_pass_name("draw_pass");
+            render_pass.draw(0..6, 0..2);
+            builder.push(render_pass);
+        }
+
+        frame_graph.compile().unwrap();
+        let capture = frame_graph
+            .compiled_frame_graph
+            .as_mut()
+            .unwrap()
+            .take_frame_capture()
+            .unwrap();
+
+        let json = capture.to_json().unwrap();
+        assert!(json.contains("\"Draw\""));
+
+        let parsed = FrameCapture::from_json(&json).unwrap();
+        assert_eq!(parsed.resources, capture.resources);
+        assert_eq!(parsed.passes, capture.passes);
+    }
+}
@@ -0,0 +1,524 @@
+use std::{fmt, sync::Arc};
+
+use wgpu::{
+    Device, Origin3d, Queue, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+    TextureDescriptor, TextureDimension, TextureUsages, TextureViewDescriptor,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+    AnyTransientResourceDescriptor, ArcAnyTransientResource, CaptureObjectResolver,
+    CapturedBindGroup, CapturedBindGroupBuffer, CapturedBindGroupResource,
+    CapturedBindGroupTextureView, CapturedPassCommand, CapturedRenderPass,
+    CapturedRenderPassCommand, CapturedResource, CapturedResourceRef, CapturedTextureView,
+    ExecuteBundleParameter, FrameCapture, FrameGraph, ImportedTextureView, IndexHandle, Pass,
+    RawResourceHandle, RenderPass, RenderPassCommand, ResourceKindSnapshot, ResourceNode,
+    ResourceRef, SetBindGroupParameter, SetIndexBufferParameter, SetRenderPipelineParameter,
+    SetVertexBufferParameter, TextureViewEdge, TransientBindGroup, TransientBindGroupBuffer,
+    TransientBindGroupEntry, TransientBindGroupResource, TransientBindGroupTextureView,
+    TransientBuffer, TransientRenderBundle, TransientRenderPassColorAttachment,
+    TransientRenderPassDepthStencilAttachment, TransientRenderPassDescriptor, TransientResource,
+    TransientResourceDescriptor, TransientTexture, TransientTextureView, VirtualResource,
+};
+
+use super::contents::TextureReadbackLayout;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    FrameGraphNotEmpty,
+    MissingObject { kind: &'static str, index: usize },
+    UnsupportedCommand { pass: String, command: String },
+    ContentsSizeMismatch { resource: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::FrameGraphNotEmpty => {
+                write!(
+                    f,
+                    "frame capture must be replayed into an empty frame graph"
+                )
+            }
+            ReplayError::MissingObject { kind, index } => {
+                write!(
+                    f,
+                    "capture references {kind} {index} which was not provided"
+                )
+            }
+            ReplayError::UnsupportedCommand { pass, command } => {
+                write!(f, "pass {pass} contains unsupported command {command}")
+            }
+            ReplayError::ContentsSizeMismatch { resource } => {
+                write!(
+                    f,
+                    "captured contents of {resource} do not match its descriptor"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+fn resolve<T>(object: Option<T>, kind: &'static str, index: usize) -> Result<T, ReplayError> {
+    object.ok_or(ReplayError::MissingObject { kind, index })
+}
+
+fn create_imported_resource(
+    resource: &CapturedResource,
+    device: &Device,
+    queue: &Queue,
+) -> Result<ArcAnyTransientResource, ReplayError> {
+    match &resource.desc {
+        AnyTransientResourceDescriptor::Buffer(desc) => {
+            let buffer = match &resource.contents {
+                Some(contents) => device.create_buffer_init(&BufferInitDescriptor {
+                    label: desc.label.as_deref(),
+                    contents,
+                    usage: desc.usage,
+                }),
+                None => device.create_buffer(&desc.get_desc()),
+            };
+
+            Ok(ArcAnyTransientResource::Buffer(Arc::new(TransientBuffer {
+                resource: buffer,
+                desc: desc.clone(),
+            })))
+        }
+        AnyTransientResourceDescriptor::Texture(desc) => {
+            let mut texture_desc = desc.get_desc();
+            if resource.contents.is_some() {
+                texture_desc.usage |= TextureUsages::COPY_DST;
+            }
+            let texture = device.create_texture(&texture_desc);
+
+            if let Some(contents) = &resource.contents {
+                let mut offset = 0;
+
+                for mip_level in 0..desc.mip_level_count {
+                    let Some(layout) = TextureReadbackLayout::new(desc, mip_level) else {
+                        break;
+                    };
+                    let end = offset + layout.unpadded_size();
+                    let Some(data) = contents.get(offset..end) else {
+                        return Err(ReplayError::ContentsSizeMismatch {
+                            resource: resource.name.clone(),
+                        });
+                    };
+
+                    queue.write_texture(
+                        TexelCopyTextureInfo {
+                            texture: &texture,
+                            mip_level,
+                            origin: Origin3d::ZERO,
+                            aspect: TextureAspect::All,
+                        },
+                        data,
+                        TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(layout.unpadded_bytes_per_row),
+                            rows_per_image: Some(layout.rows),
+                        },
+                        desc.size.mip_level_size(mip_level, desc.dimension),
+                    );
+                    offset = end;
+                }
+            }
+
+            Ok(ArcAnyTransientResource::Texture(Arc::new(
+                TransientTexture {
+                    resource: texture,
+                    desc: desc.clone(),
+                },
+            )))
+        }
+        AnyTransientResourceDescriptor::TextureView(desc) => {
+            let texture = device.create_texture(&TextureDescriptor {
+                label: desc.label.as_deref(),
+                size: desc.size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: desc.format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+
+            Ok(ArcAnyTransientResource::TextureView(Arc::new(
+                ImportedTextureView {
+                    resource: texture.create_view(&TextureViewDescriptor::default()),
+                    desc: desc.clone(),
+                },
+            )))
+        }
+    }
+}
+
+impl CapturedResourceRef {
+    fn to_raw(&self) -> RawResourceHandle {
+        RawResourceHandle {
+            index: IndexHandle::new(self.resource),
+            version: self.version,
+        }
+    }
+}
+
+impl FrameCapture {
+    fn resource_ref<ResourceType: TransientResource, ViewType>(
+        &self,
+        resource_ref: &CapturedResourceRef,
+    ) -> ResourceRef<ResourceType, ViewType> {
+        let desc = &self.resources[resource_ref.resource].desc;
+
+        ResourceRef::new(
+            resource_ref.to_raw(),
+            ResourceType::Descriptor::borrow_resource_descriptor(desc).clone(),
+        )
+    }
+
+    fn texture_view_edge(
+        &self,
+        view: &CapturedTextureView,
+        objects: &dyn CaptureObjectResolver,
+    ) -> Result<TextureViewEdge, ReplayError> {
+        let edge = match view {
+            CapturedTextureView::Read { texture, desc } => {
+                TextureViewEdge::Read(TransientTextureView {
+                    texture: self.resource_ref(texture),
+                    desc: desc.clone(),
+                })
+            }
+            CapturedTextureView::Write { texture, desc } => {
+                TextureViewEdge::Write(TransientTextureView {
+                    texture: self.resource_ref(texture),
+                    desc: desc.clone(),
+                })
+            }
+            CapturedTextureView::ImportedRead(texture_view) => {
+                TextureViewEdge::ImportedRead(self.resource_ref(texture_view))
+            }
+            CapturedTextureView::ImportedWrite(texture_view) => {
+                TextureViewEdge::ImportedWrite(self.resource_ref(texture_view))
+            }
+            CapturedTextureView::Owned(index) => TextureViewEdge::Owned(resolve(
+                objects.texture_view(*index),
+                "texture view",
+                *index,
+            )?),
+        };
+
+        Ok(edge)
+    }
+
+    fn bind_group_buffer<ViewType>(
+        &self,
+        binding: &CapturedBindGroupBuffer,
+    ) -> TransientBindGroupBuffer<ViewType> {
+        TransientBindGroupBuffer {
+            buffer: self.resource_ref(&binding.buffer),
+            size: binding.size,
+            offset: binding.offset,
+        }
+    }
+
+    fn bind_group_texture_view<ViewType>(
+        &self,
+        binding: &CapturedBindGroupTextureView,
+    ) -> TransientBindGroupTextureView<ViewType> {
+        TransientBindGroupTextureView {
+            texture: self.resource_ref(&binding.texture),
+            texture_view_desc: binding.desc.clone(),
+        }
+    }
+
+    fn bind_group(
+        &self,
+        bind_group: &CapturedBindGroup,
+        objects: &dyn CaptureObjectResolver,
+    ) -> Result<TransientBindGroup, ReplayError> {
+        let entries = bind_group
+            .entries
+            .iter()
+            .map(|entry| {
+                let resource = match &entry.resource {
+                    CapturedBindGroupResource::Buffer(binding) => {
+                        TransientBindGroupResource::Buffer(self.bind_group_buffer(binding))
+                    }
+                    CapturedBindGroupResource::BufferArray(bindings) => {
+                        TransientBindGroupResource::BufferArray(
+                            bindings
+                                .iter()
+                                .map(|binding| self.bind_group_buffer(binding))
+                                .collect(),
+                        )
+                    }
+                    CapturedBindGroupResource::ReadWriteStorageBuffer(binding) => {
+                        TransientBindGroupResource::ReadWriteStorageBuffer(
+                            self.bind_group_buffer(binding),
+                        )
+                    }
+                    CapturedBindGroupResource::WriteOnlyStorageBuffer(binding) => {
+                        TransientBindGroupResource::WriteOnlyStorageBuffer(
+                            self.bind_group_buffer(binding),
+                        )
+                    }
+                    CapturedBindGroupResource::Sampler(index) => {
+                        TransientBindGroupResource::Sampler(resolve(
+                            objects.sampler(*index),
+                            "sampler",
+                            *index,
+                        )?)
+                    }
+                    CapturedBindGroupResource::SamplerArray(indices) => {
+                        TransientBindGroupResource::SamplerArray(
+                            indices
+                                .iter()
+                                .map(|index| resolve(objects.sampler(*index), "sampler", *index))
+                                .collect::<Result<_, _>>()?,
+                        )
+                    }
+                    CapturedBindGroupResource::TextureView(binding) => {
+                        TransientBindGroupResource::TextureView(
+                            self.bind_group_texture_view(binding),
+                        )
+                    }
+                    CapturedBindGroupResource::TextureViewArray(bindings) => {
+                        TransientBindGroupResource::TextureViewArray(
+                            bindings
+                                .iter()
+                                .map(|binding| self.bind_group_texture_view(binding))
+                                .collect(),
+                        )
+                    }
+                    CapturedBindGroupResource::ReadWriteStorageTextureView(binding) => {
+                        TransientBindGroupResource::ReadWriteStorageTextureView(
+                            self.bind_group_texture_view(binding),
+                        )
+                    }
+                    CapturedBindGroupResource::WriteOnlyStorageTextureView(binding) => {
+                        TransientBindGroupResource::WriteOnlyStorageTextureView(
+                            self.bind_group_texture_view(binding),
+                        )
+                    }
+                };
+
+                Ok(TransientBindGroupEntry {
+                    binding: entry.binding,
+                    resource,
+                })
+            })
+            .collect::<Result<_, ReplayError>>()?;
+
+        Ok(TransientBindGroup {
+            label: bind_group.label.clone(),
+            layout: resolve(
+                objects.bind_group_layout(bind_group.layout),
+                "bind group layout",
+                bind_group.layout,
+            )?,
+            entries,
+        })
+    }
+
+    fn render_pass_commands(
+        &self,
+        pass: &str,
+        commands: &[CapturedRenderPassCommand],
+        objects: &dyn CaptureObjectResolver,
+    ) -> Result<Vec<Box<dyn RenderPassCommand>>, ReplayError> {
+        commands
+            .iter()
+            .map(
+                |command| -> Result<Box<dyn RenderPassCommand>, ReplayError> {
+                    let command: Box<dyn RenderPassCommand> = match command {
+                        CapturedRenderPassCommand::SetBindGroup {
+                            index,
+                            bind_group,
+                            offsets,
+                        } => Box::new(SetBindGroupParameter {
+                            index: *index,
+                            bind_group: self.bind_group(bind_group, objects)?,
+                            offsets: offsets.clone(),
+                        }),
+                        CapturedRenderPassCommand::SetRenderPipeline(index) => {
+                            Box::new(SetRenderPipelineParameter {
+                                pipeline: resolve(
+                                    objects.render_pipeline(*index),
+                                    "render pipeline",
+                                    *index,
+                                )?,
+                            })
+                        }
+                        CapturedRenderPassCommand::SetVertexBuffer {
+                            slot,
+                            buffer,
+                            offset,
+                            size,
+                        } => Box::new(SetVertexBufferParameter {
+                            slot: *slot,
+                            buffer_ref: self.resource_ref(buffer),
+                            offset: *offset,
+                            size: *size,
+                        }),
+                        CapturedRenderPassCommand::SetIndexBuffer {
+                            buffer,
+                            index_format,
+                            offset,
+                            size,
+                        } => Box::new(SetIndexBufferParameter {
+                            buffer_ref: self.resource_ref(buffer),
+                            index_format: *index_format,
+                            offset: *offset,
+                            size: *size,
+                        }),
+                        CapturedRenderPassCommand::Draw(parameter) => Box::new(parameter.clone()),
+                        CapturedRenderPassCommand::DrawIndexed(parameter) => {
+                            Box::new(parameter.clone())
+                        }
+                        CapturedRenderPassCommand::ExecuteBundle { desc, commands } => {
+                            Box::new(ExecuteBundleParameter {
+                                bundle: TransientRenderBundle {
+                                    desc: desc.clone(),
+                                    commands: self.render_pass_commands(pass, commands, objects)?,
+                                },
+                            })
+                        }
+                        CapturedRenderPassCommand::Unsupported(command) => {
+                            return Err(ReplayError::UnsupportedCommand {
+                                pass: pass.to_string(),
+                                command: command.clone(),
+                            });
+                        }
+                    };
+
+                    Ok(command)
+                },
+            )
+            .collect()
+    }
+
+    fn render_pass(
+        &self,
+        pass: &str,
+        render_pass: &CapturedRenderPass,
+        objects: &dyn CaptureObjectResolver,
+    ) -> Result<RenderPass, ReplayError> {
+        let desc = TransientRenderPassDescriptor {
+            label: render_pass.label.clone(),
+            color_attachments: render_pass
+                .color_attachments
+                .iter()
+                .map(|color_attachment| {
+                    color_attachment
+                        .as_ref()
+                        .map(|color_attachment| {
+                            Ok(TransientRenderPassColorAttachment {
+                                view: self.texture_view_edge(&color_attachment.view, objects)?,
+                                depth_slice: color_attachment.depth_slice,
+                                resolve_target: color_attachment
+                                    .resolve_target
+                                    .as_ref()
+                                    .map(|resolve_target| {
+                                        self.texture_view_edge(resolve_target, objects)
+                                    })
+                                    .transpose()?,
+                                ops: color_attachment.ops,
+                            })
+                        })
+                        .transpose()
+                })
+                .collect::<Result<_, ReplayError>>()?,
+            depth_stencil_attachment: render_pass
+                .depth_stencil_attachment
+                .as_ref()
+                .map(|depth_stencil_attachment| {
+                    Ok::<_, ReplayError>(TransientRenderPassDepthStencilAttachment {
+                        view: self.texture_view_edge(&depth_stencil_attachment.view, objects)?,
+                        depth_ops: depth_stencil_attachment.depth_ops,
+                        stencil_ops: depth_stencil_attachment.stencil_ops,
+                    })
+                })
+                .transpose()?,
+        };
+
+        Ok(RenderPass {
+            desc,
+            commands: self.render_pass_commands(pass, &render_pass.commands, objects)?,
+        })
+    }
+
+    pub fn replay(
+        &self,
+        frame_graph: &mut FrameGraph,
+        device: &Device,
+        queue: &Queue,
+    ) -> Result<(), ReplayError> {
+        self.replay_with(frame_graph, device, queue, &self.objects)
+    }
+
+    pub fn replay_with(
+        &self,
+        frame_graph: &mut FrameGraph,
+        device: &Device,
+        queue: &Queue,
+        objects: &dyn CaptureObjectResolver,
+    ) -> Result<(), ReplayError> {
+        if !frame_graph.resource_nodes.is_empty() {
+            return Err(ReplayError::FrameGraphNotEmpty);
+        }
+
+        let mut passes = vec![];
+        for captured_pass in self.passes.iter() {
+            let mut pass = Pass::default();
+            pass.label = captured_pass.label.clone();
+
+            for command in captured_pass.commands.iter() {
+                match command {
+                    CapturedPassCommand::RenderPass(render_pass) => {
+                        pass.push(self.render_pass(&captured_pass.name, render_pass, objects)?)
+                    }
+                    CapturedPassCommand::Unsupported(command) => {
+                        return Err(ReplayError::UnsupportedCommand {
+                            pass: captured_pass.name.clone(),
+                            command: command.clone(),
+                        });
+                    }
+                }
+            }
+
+            passes.push(pass);
+        }
+
+        for (index, resource) in self.resources.iter().enumerate() {
+            let virtual_resource = match resource.kind {
+                ResourceKindSnapshot::Transient => VirtualResource::Setuped(resource.desc.clone()),
+                ResourceKindSnapshot::Imported | ResourceKindSnapshot::History { .. } => {
+                    VirtualResource::Imported(create_imported_resource(resource, device, queue)?)
+                }
+            };
+
+            let mut resource_node =
+                ResourceNode::new(&resource.name, IndexHandle::new(index), virtual_resource);
+            resource_node.output = resource.output;
+            resource_node.exported = resource.exported;
+            resource_node.range_writes = resource.range_writes.clone();
+            if resource.kind == ResourceKindSnapshot::Transient {
+                resource_node.contents = resource.contents.clone().unwrap_or_default();
+            }
+
+            frame_graph.resource_nodes.push(resource_node);
+            frame_graph.insert(&resource.name, IndexHandle::new(index));
+        }
+
+        for (captured_pass, pass) in self.passes.iter().zip(passes) {
+            let pass_node = frame_graph.pass_node(&captured_pass.name);
+            pass_node.reads = captured_pass.reads.iter().map(|r| r.to_raw()).collect();
+            pass_node.writes = captured_pass.writes.iter().map(|r| r.to_raw()).collect();
+            pass_node.dependencies = captured_pass.dependencies.clone();
+            pass_node.pass = Some(pass);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,357 @@
+use wgpu::{
+    Buffer, BufferUsages, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, Color,
+    CommandBuffer, CommandEncoder, CommandEncoderDescriptor, Device, LoadOp, Operations, Origin3d,
+    RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, StoreOp,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+    TextureDimension, TextureUsages, TextureViewDescriptor, TextureViewDimension,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{AnyTransientResource, TransientBuffer, TransientTexture};
+
+pub const DEBUG_FILL_BYTE: u8 = 0xff;
+
+pub const DEBUG_FILL_COLOR: Color = Color {
+    r: 1.0,
+    g: 0.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+pub const DEBUG_FILL_DEPTH: f32 = 0.5;
+
+pub const DEBUG_FILL_STENCIL: u32 = 0xff;
+
+pub struct DebugFill {
+    device: Device,
+    pattern: Option<Buffer>,
+    command_encoder: Option<CommandEncoder>,
+    filled_count: u64,
+}
+
+impl DebugFill {
+    pub fn new(device: &Device) -> Self {
+        Self {
+            device: device.clone(),
+            pattern: None,
+            command_encoder: None,
+            filled_count: 0,
+        }
+    }
+
+    pub fn filled_count(&self) -> u64 {
+        self.filled_count
+    }
+
+    pub fn fill(&mut self, name: &str, resource: &AnyTransientResource) {
+        let filled = match resource {
+            AnyTransientResource::OwnedBuffer(buffer) => self.fill_buffer(name, buffer),
+            AnyTransientResource::OwnedTexture(texture) => self.fill_texture(name, texture),
+            _ => false,
+        };
+
+        if filled {
+            self.filled_count += 1;
+        }
+    }
+
+    pub fn finish(&mut self) -> Option<CommandBuffer> {
+        self.command_encoder
+            .take()
+            .map(|command_encoder| command_encoder.finish())
+    }
+
+    fn command_encoder(&mut self) -> &mut CommandEncoder {
+        self.command_encoder.get_or_insert_with(|| {
+            self.device
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some("debug_fill"),
+                })
+        })
+    }
+
+    fn pattern(&mut self, size: u64) -> Buffer {
+        if let Some(pattern) = &self.pattern
+            && pattern.size() >= size
+        {
+            return pattern.clone();
+        }
+
+        let pattern = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("debug_fill_pattern"),
+            contents: &vec![DEBUG_FILL_BYTE; size as usize],
+            usage: BufferUsages::COPY_SRC,
+        });
+        self.pattern = Some(pattern.clone());
+
+        pattern
+    }
+
+    fn skip(name: &str, reason: &str) -> bool {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(resource = %name, "skipped debug fill of {reason}");
+        #[cfg(not(feature = "tracing"))]
+        let _ = (name, reason);
+
+        false
+    }
+
+    fn fill_buffer(&mut self, name: &str, buffer: &TransientBuffer) -> bool {
+        if !buffer.desc.usage.contains(BufferUsages::COPY_DST) {
+            return Self::skip(name, "buffer without COPY_DST");
+        }
+
+        if buffer.desc.mapped_at_creation {
+            return Self::skip(name, "buffer mapped at creation");
+        }
+
+        let size = buffer.desc.size / COPY_BUFFER_ALIGNMENT * COPY_BUFFER_ALIGNMENT;
+        if size == 0 {
+            return Self::skip(name, "buffer smaller than the copy alignment");
+        }
+
+        let pattern = self.pattern(size);
+        self.command_encoder()
+            .copy_buffer_to_buffer(&pattern, 0, &buffer.resource, 0, size);
+
+        true
+    }
+
+    fn fill_texture(&mut self, name: &str, texture: &TransientTexture) -> bool {
+        let desc = &texture.desc;
+
+        if desc.usage.contains(TextureUsages::RENDER_ATTACHMENT) {
+            self.clear_texture(texture);
+            return true;
+        }
+
+        if desc.format.is_depth_stencil_format() {
+            return Self::skip(name, "depth/stencil texture without RENDER_ATTACHMENT");
+        }
+
+        if !desc.usage.contains(TextureUsages::COPY_DST) {
+            return Self::skip(name, "texture without COPY_DST");
+        }
+
+        if desc.sample_count > 1 {
+            return Self::skip(name, "multisampled texture without RENDER_ATTACHMENT");
+        }
+
+        if !self.copy_texture(texture) {
+            return Self::skip(name, "texture whose format has no block copy size");
+        }
+
+        true
+    }
+
+    fn clear_texture(&mut self, texture: &TransientTexture) {
+        let desc = &texture.desc;
+        let format = desc.format;
+
+        for mip_level in 0..desc.mip_level_count {
+            let size = desc.size.mip_level_size(mip_level, desc.dimension);
+
+            let (dimension, layers, depth_slices) = match desc.dimension {
+                TextureDimension::D3 => (TextureViewDimension::D3, 1, size.depth_or_array_layers),
+                _ => (TextureViewDimension::D2, size.depth_or_array_layers, 1),
+            };
+
+            for layer in 0..layers {
+                let view = texture.resource.create_view(&TextureViewDescriptor {
+                    label: Some("debug_fill"),
+                    dimension: Some(dimension),
+                    base_mip_level: mip_level,
+                    mip_level_count: Some(1),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                });
+
+                if format.is_depth_stencil_format() {
+                    self.command_encoder()
+                        .begin_render_pass(&RenderPassDescriptor {
+                            label: Some("debug_fill"),
+                            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                                view: &view,
+                                depth_ops: format.has_depth_aspect().then_some(Operations {
+                                    load: LoadOp::Clear(DEBUG_FILL_DEPTH),
+                                    store: StoreOp::Store,
+                                }),
+                                stencil_ops: format.has_stencil_aspect().then_some(Operations {
+                                    load: LoadOp::Clear(DEBUG_FILL_STENCIL),
+                                    store: StoreOp::Store,
+                                }),
+                            }),
+                            ..Default::default()
+                        });
+                    continue;
+                }
+
+                for depth_slice in 0..depth_slices {
+                    self.command_encoder()
+                        .begin_render_pass(&RenderPassDescriptor {
+                            label: Some("debug_fill"),
+                            color_attachments: &[Some(RenderPassColorAttachment {
+                                view: &view,
+                                depth_slice: (dimension == TextureViewDimension::D3)
+                                    .then_some(depth_slice),
+                                resolve_target: None,
+                                ops: Operations {
+                                    load: LoadOp::Clear(DEBUG_FILL_COLOR),
+                                    store: StoreOp::Store,
+                                },
+                            })],
+                            ..Default::default()
+                        });
+                }
+            }
+        }
+    }
+
+    fn copy_texture(&mut self, texture: &TransientTexture) -> bool {
+        let desc = &texture.desc;
+        let Some(block_size) = desc.format.block_copy_size(None) else {
+            return false;
+        };
+        let (block_width, block_height) = desc.format.block_dimensions();
+
+        for mip_level in 0..desc.mip_level_count {
+            let size = desc
+                .size
+                .mip_level_size(mip_level, desc.dimension)
+                .physical_size(desc.format);
+            let bytes_per_row = (size.width.div_ceil(block_width) * block_size)
+                .next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+            let rows = size.height.div_ceil(block_height);
+
+            let pattern = self
+                .pattern(bytes_per_row as u64 * rows as u64 * size.depth_or_array_layers as u64);
+            self.command_encoder().copy_buffer_to_texture(
+                TexelCopyBufferInfo {
+                    buffer: &pattern,
+                    layout: TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(bytes_per_row),
+                        rows_per_image: Some(rows),
+                    },
+                },
+                TexelCopyTextureInfo {
+                    texture: &texture.resource,
+                    mip_level,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                size,
+            );
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{BufferUsages, Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+    use crate::{
+        FrameGraph, FrameGraphContext, PassNodeBuilderExt, PipelineContainer,
+        TransientBufferDescriptor, TransientResourceCache, TransientTextureDescriptor,
+    };
+
+    fn texture_desc(format: TextureFormat, usage: TextureUsages) -> TransientTextureDescriptor {
+        TransientTextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: 16,
+                height: 16,
+                depth_or_array_layers: 2,
+            },
+            mip_level_count: 2,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage,
+            view_formats: vec![],
+        }
+    }
+
+    #[test]
+    fn debug_fill_clears_fillable_transient_resources() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+
+        let fillable = frame_graph.create(
+            "fillable",
+            TransientBufferDescriptor {
+                label: None,
+                size: 30,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        let storage_only = frame_graph.create(
+            "storage_only",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        let color = frame_graph.create(
+            "color",
+            texture_desc(TextureFormat::Rgba8Unorm, TextureUsages::RENDER_ATTACHMENT),
+        );
+        let lut = frame_graph.create(
+            "lut",
+            texture_desc(TextureFormat::R32Float, TextureUsages::COPY_DST),
+        );
+        let depth = frame_graph.create(
+            "depth",
+            texture_desc(
+                TextureFormat::Depth32Float,
+                TextureUsages::RENDER_ATTACHMENT,
+            ),
+        );
+        let imported = frame_graph.import_buffer(
+            "imported",
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        );
+        frame_graph.mark_output(&fillable);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("write_all");
+            builder.write(fillable);
+            builder.write(storage_only);
+            builder.write(color);
+            builder.write(lut);
+            builder.write(depth);
+            builder.write(imported);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        )
+        .with_debug_fill(true);
+        frame_graph.execute(&mut context);
+
+        assert_eq!(
+            context.resource_table.debug_fill().unwrap().filled_count(),
+            4
+        );
+        assert_eq!(context.command_buffers.len(), 1);
+
+        queue.submit(context.finish(&queue));
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompileError {
+    UnknownDependency {
+        pass: String,
+        dependency: String,
+    },
+    CyclicDependencies {
+        passes: Vec<String>,
+    },
+    UnusedExport {
+        resource: String,
+    },
+    RangeHazard {
+        pass: String,
+        resource: String,
+    },
+    BufferRangeOutOfBounds {
+        pass: String,
+        resource: String,
+        offset: u64,
+        size: u64,
+        buffer_size: u64,
+    },
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::UnknownDependency { pass, dependency } => {
+                write!(f, "pass {pass} depends on unknown pass {dependency}")
+            }
+            CompileError::CyclicDependencies { passes } => {
+                write!(
+                    f,
+                    "frame graph contains cyclic pass dependencies between {}",
+                    passes.join(", ")
+                )
+            }
+            CompileError::UnusedExport { resource } => {
+                write!(f, "exported resource {resource} is never used by a pass")
+            }
+            CompileError::RangeHazard { pass, resource } => {
+                write!(
+                    f,
+                    "pass {pass} reads and writes partially overlapping ranges of {resource}"
+                )
+            }
+            CompileError::BufferRangeOutOfBounds {
+                pass,
+                resource,
+                offset,
+                size,
+                buffer_size,
+            } => {
+                write!(
+                    f,
+                    "pass {pass} accesses range {offset}+{size} of buffer {resource} with size {buffer_size}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
@@ -1,5 +1,12 @@
+use std::time::{Duration, Instant};
+
+use wgpu::{BufferUsages, COPY_BUFFER_ALIGNMENT, CommandEncoderDescriptor};
+
 use crate::{
-    FrameGraph, FrameGraphContext, IndexHandle, Pass, PassNode, ResourceRelease, ResourceRequese,
+    AnyTransientResource, AnyTransientResourceDescriptor, ErrorScope, FrameGraph,
+    FrameGraphContext, IndexHandle, Pass, PassErrorStage, PassNode, PassStats, PendingErrorScope,
+    PendingPassError, ResourceAcquisition, ResourceNode, ResourceRelease, ResourceRequese,
+    TransientBufferDescriptor,
 };
 
 #[derive(Default)]
@@ -7,40 +14,227 @@ pub struct DevicePass {
     pub pass: Option<Pass>,
     pub resource_release_array: Vec<ResourceRelease>,
     pub resource_request_array: Vec<ResourceRequese>,
+    pub resources: Vec<(IndexHandle<ResourceNode>, String)>,
     pub name: String,
 }
 
+pub struct AcquisitionTiming {
+    pub resource: String,
+    pub acquisition: ResourceAcquisition,
+    pub start: Instant,
+}
+
+pub struct DevicePassTimings {
+    pub request_start: Instant,
+    pub request: Duration,
+    pub record_start: Instant,
+    pub record: Duration,
+    pub release_start: Instant,
+    pub release: Duration,
+    pub acquisitions: Vec<AcquisitionTiming>,
+}
+
+impl DevicePassTimings {
+    pub fn total(&self) -> Duration {
+        self.request + self.record + self.release
+    }
+}
+
 impl DevicePass {
-    pub fn request_resources(&self, context: &mut FrameGraphContext) {
+    pub fn request_resources(&self, context: &mut FrameGraphContext) -> Vec<AcquisitionTiming> {
+        let device = context.device().clone();
+        let error_scope = context.error_scopes.then(|| ErrorScope::push(&device));
+        let mut acquisitions = vec![];
+
         for resource in self.resource_request_array.iter() {
-            context.resource_table.request_resource(
+            let start = Instant::now();
+            let acquisition = context.resource_table.request_resource(
                 resource,
-                &context.device,
+                context.resource_creator.as_ref(),
                 context.transient_resource_cache,
             );
+            context.stats.record_acquisition(resource, acquisition);
+            acquisitions.push(AcquisitionTiming {
+                resource: resource.name.clone(),
+                acquisition,
+                start,
+            });
+        }
+
+        if let Some(command_buffer) = context.resource_table.finish_debug_fill() {
+            context.add_command_buffer(command_buffer);
+        }
+
+        for resource in self.resource_request_array.iter() {
+            if !resource.contents.is_empty() {
+                Self::upload_contents(resource, context);
+            }
+        }
+
+        if let Some(error_scope) = error_scope {
+            let resources = self
+                .resource_request_array
+                .iter()
+                .map(|resource| resource.index)
+                .collect::<Vec<_>>();
+            self.report_errors(
+                context,
+                PassErrorStage::Acquisition,
+                &resources,
+                error_scope.pop(),
+            );
         }
+
+        acquisitions
+    }
+
+    fn resource_label(
+        &self,
+        context: &FrameGraphContext,
+        index: &IndexHandle<ResourceNode>,
+    ) -> Option<String> {
+        context
+            .resource_table
+            .get_any_resource(index)
+            .and_then(|resource| resource.label())
+            .map(ToString::to_string)
+            .or_else(|| {
+                self.resources
+                    .iter()
+                    .find(|(resource, _)| resource == index)
+                    .map(|(_, name)| name.clone())
+            })
+    }
+
+    fn report_errors(
+        &self,
+        context: &mut FrameGraphContext,
+        stage: PassErrorStage,
+        resources: &[IndexHandle<ResourceNode>],
+        scope: PendingErrorScope,
+    ) {
+        let resources = resources
+            .iter()
+            .filter_map(|index| self.resource_label(context, index))
+            .collect();
+
+        context.push_pending_pass_error(PendingPassError {
+            pass: self.name.clone(),
+            stage,
+            resources,
+            scope,
+            errors: vec![],
+        });
+    }
+
+    fn upload_contents(resource: &ResourceRequese, context: &mut FrameGraphContext) {
+        let Some(AnyTransientResource::OwnedBuffer(buffer)) =
+            context.resource_table.get_any_resource(&resource.index)
+        else {
+            return;
+        };
+
+        let size = (resource.contents.len() as u64).next_multiple_of(COPY_BUFFER_ALIGNMENT);
+        let AnyTransientResource::OwnedBuffer(staging_buffer) = context
+            .resource_creator
+            .create_resource(&AnyTransientResourceDescriptor::Buffer(
+                TransientBufferDescriptor {
+                    label: Some("upload_contents".to_string()),
+                    size,
+                    usage: BufferUsages::COPY_SRC,
+                    mapped_at_creation: true,
+                },
+            ))
+        else {
+            unreachable!("buffer descriptors create owned buffers")
+        };
+
+        staging_buffer.resource.get_mapped_range_mut(..)[..resource.contents.len()]
+            .copy_from_slice(&resource.contents);
+        staging_buffer.resource.unmap();
+
+        let mut command_encoder = context
+            .device()
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        command_encoder.copy_buffer_to_buffer(
+            &staging_buffer.resource,
+            0,
+            &buffer.resource,
+            0,
+            resource.contents.len() as u64,
+        );
+
+        context.add_command_buffer(command_encoder.finish());
     }
 
     pub fn release_resources(&self, context: &mut FrameGraphContext) {
         for handle in self.resource_release_array.iter() {
+            context.stats.record_release(handle);
             context
                 .resource_table
                 .release_resource(handle, context.transient_resource_cache);
         }
     }
 
-    pub fn execute(&self, context: &mut FrameGraphContext) {
-        self.request_resources(context);
+    pub fn execute(&self, context: &mut FrameGraphContext) -> DevicePassTimings {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("device_pass", name = %self.name).entered();
+
+        let request_start = Instant::now();
+        let acquisitions = self.request_resources(context);
+        let request = request_start.elapsed();
+
+        let record_start = Instant::now();
+        let stats = self.record(context);
+        let record = record_start.elapsed();
+
+        let release_start = Instant::now();
+        self.release_resources(context);
+        let release = release_start.elapsed();
 
-        if let Some(pass) = &self.pass {
-            pass.render(
+        context.stats.push_pass(stats);
+
+        DevicePassTimings {
+            request_start,
+            request,
+            record_start,
+            record,
+            release_start,
+            release,
+            acquisitions,
+        }
+    }
+
+    pub fn record(&self, context: &mut FrameGraphContext) -> PassStats {
+        let stats = match &self.pass {
+            Some(pass) if context.error_scopes => {
+                let (stats, scoped_errors) = pass.render_with_error_scopes(
+                    &mut context.command_buffers,
+                    context.resource_creator.as_ref(),
+                    &context.resource_table,
+                    &context.pipeline_container,
+                    context.transient_resource_cache,
+                );
+                for scoped in scoped_errors {
+                    self.report_errors(context, scoped.stage, &scoped.resources, scoped.scope);
+                }
+
+                stats
+            }
+            Some(pass) => pass.render(
                 &mut context.command_buffers,
-                &context.device,
+                context.resource_creator.as_ref(),
                 &context.resource_table,
                 &context.pipeline_container,
-            );
+                context.transient_resource_cache,
+            ),
+            None => PassStats::default(),
+        };
+
+        PassStats {
+            name: self.name.clone(),
+            ..stats
         }
-        self.release_resources(context);
     }
 
     pub fn extra(&mut self, graph: &mut FrameGraph, index: IndexHandle<PassNode>) {
@@ -58,6 +252,14 @@ impl DevicePass {
             .map(|handle| graph.get_resource_node(handle).release())
             .collect();
 
+        let mut resources = vec![];
+        for handle in pass_node.reads.iter().chain(pass_node.writes.iter()) {
+            if !resources.iter().any(|(index, _)| *index == handle.index) {
+                let name = graph.get_resource_node(&handle.index).name.clone();
+                resources.push((handle.index, name));
+            }
+        }
+
         let pass_node = graph.get_pass_node_mut(&index);
 
         let pass = pass_node.pass.take();
@@ -65,6 +267,7 @@ impl DevicePass {
         self.resource_request_array = resource_request_array;
         self.pass = pass;
         self.resource_release_array = resource_release_array;
+        self.resources = resources;
 
         self.name = pass_node.name.clone();
     }
@@ -0,0 +1,290 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use wgpu::{Device, Error, ErrorFilter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassErrorKind {
+    Validation,
+    OutOfMemory,
+    Internal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassErrorStage {
+    Acquisition,
+    Command(usize),
+    Finish,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassError {
+    pub pass: String,
+    pub kind: PassErrorKind,
+    pub stage: PassErrorStage,
+    pub resources: Vec<String>,
+    pub message: String,
+}
+
+impl PassError {
+    pub fn new(pass: &str, stage: PassErrorStage, resources: &[String], error: &Error) -> Self {
+        let kind = match error {
+            Error::OutOfMemory { .. } => PassErrorKind::OutOfMemory,
+            Error::Validation { .. } => PassErrorKind::Validation,
+            Error::Internal { .. } => PassErrorKind::Internal,
+        };
+
+        Self {
+            pass: pass.to_string(),
+            kind,
+            stage,
+            resources: resources.to_vec(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} error in pass {}", self.kind, self.pass)?;
+        match self.stage {
+            PassErrorStage::Acquisition => write!(f, " while acquiring resources")?,
+            PassErrorStage::Command(command_index) => write!(f, " at command {command_index}")?,
+            PassErrorStage::Finish => write!(f, " while finishing the command encoder")?,
+        }
+        if !self.resources.is_empty() {
+            write!(f, " (resources: {})", self.resources.join(", "))?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+const ERROR_FILTERS: [ErrorFilter; 3] = [
+    ErrorFilter::Internal,
+    ErrorFilter::OutOfMemory,
+    ErrorFilter::Validation,
+];
+
+type PopErrorScope = Pin<Box<dyn Future<Output = Option<Error>>>>;
+
+pub(crate) struct ErrorScope<'a> {
+    device: &'a Device,
+}
+
+impl<'a> ErrorScope<'a> {
+    pub fn push(device: &'a Device) -> Self {
+        for filter in ERROR_FILTERS {
+            device.push_error_scope(filter);
+        }
+
+        Self { device }
+    }
+
+    pub fn pop(self) -> PendingErrorScope {
+        PendingErrorScope {
+            scopes: ERROR_FILTERS
+                .iter()
+                .map(|_| Box::pin(self.device.pop_error_scope()) as PopErrorScope)
+                .collect(),
+        }
+    }
+}
+
+pub(crate) struct PendingErrorScope {
+    scopes: Vec<PopErrorScope>,
+}
+
+pub(crate) struct PendingPassError {
+    pub pass: String,
+    pub stage: PassErrorStage,
+    pub resources: Vec<String>,
+    pub scope: PendingErrorScope,
+    pub errors: Vec<Error>,
+}
+
+impl Future for PendingPassError {
+    type Output = Vec<PassError>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { scope, errors, .. } = &mut *self;
+
+        scope
+            .scopes
+            .retain_mut(|pop| match pop.as_mut().poll(context) {
+                Poll::Ready(error) => {
+                    errors.extend(error);
+                    false
+                }
+                Poll::Pending => true,
+            });
+
+        if !scope.scopes.is_empty() {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(
+            self.errors
+                .iter()
+                .map(|error| {
+                    let pass_error = PassError::new(&self.pass, self.stage, &self.resources, error);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(pass = %self.pass, "{pass_error}");
+
+                    pass_error
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use wgpu::{BufferDescriptor, BufferUsages};
+
+    use crate::{
+        FrameGraph, FrameGraphContext, PassCommand, PassContext, PassErrorKind, PassErrorStage,
+        PassNodeBuilderExt, PipelineContainer, ResourceRef, ResourceWrite, TransientBuffer,
+        TransientBufferDescriptor, TransientResourceCache,
+    };
+
+    struct CreateBuffer {
+        target: ResourceRef<TransientBuffer, ResourceWrite>,
+        usage: BufferUsages,
+    }
+
+    impl PassCommand for CreateBuffer {
+        fn execute(&self, context: &mut PassContext) {
+            let _ = context.get_resource(&self.target);
+            let _ = context.device().create_buffer(&BufferDescriptor {
+                label: None,
+                size: 64,
+                usage: self.usage,
+                mapped_at_creation: false,
+            });
+        }
+    }
+
+    #[test]
+    fn error_scopes_attribute_validation_errors_to_pass_commands() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+
+        let buffer_desc = TransientBufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        };
+        let input = frame_graph.create("input", buffer_desc.clone());
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: Some("readback_output".to_string()),
+                ..buffer_desc
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("readback");
+            let input = builder.write(input);
+            let output = builder.write(output);
+
+            builder.push(CreateBuffer {
+                target: input,
+                usage: BufferUsages::COPY_DST,
+            });
+            builder.push(CreateBuffer {
+                target: output,
+                usage: BufferUsages::MAP_READ | BufferUsages::STORAGE,
+            });
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        )
+        .with_error_scopes(true);
+        frame_graph.execute(&mut context);
+
+        assert!(!context.has_pending_pass_errors());
+        assert_eq!(context.command_buffers.len(), 1);
+
+        let errors = context.take_pass_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pass, "readback");
+        assert_eq!(errors[0].kind, PassErrorKind::Validation);
+        assert_eq!(errors[0].stage, PassErrorStage::Command(1));
+        assert_eq!(errors[0].resources, vec!["readback_output"]);
+        assert!(errors[0].to_string().starts_with(
+            "Validation error in pass readback at command 1 (resources: readback_output)"
+        ));
+
+        queue.submit(context.finish(&queue));
+    }
+
+    #[test]
+    fn error_scopes_name_unlabeled_resources_by_graph_name() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("readback");
+            let output = builder.write(output);
+
+            builder.push(CreateBuffer {
+                target: output,
+                usage: BufferUsages::MAP_READ | BufferUsages::STORAGE,
+            });
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        )
+        .with_error_scopes(true);
+        frame_graph.execute(&mut context);
+
+        let Poll::Ready(errors) =
+            pin!(context.resolve_pass_errors()).poll(&mut Context::from_waker(Waker::noop()))
+        else {
+            panic!("error scopes resolve immediately on native backends");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].stage, PassErrorStage::Command(0));
+        assert_eq!(errors[0].resources, vec!["output"]);
+
+        queue.submit(context.finish(&queue));
+    }
+}
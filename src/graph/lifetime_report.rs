@@ -0,0 +1,413 @@
+use std::{collections::HashMap, fmt::Write as _, fs, io, path::Path};
+
+use crate::{AnyTransientResourceDescriptor, FrameGraph, VirtualResource};
+
+const SVG_LABEL_WIDTH: usize = 180;
+const SVG_PASS_WIDTH: usize = 120;
+const SVG_ROW_HEIGHT: usize = 24;
+const SVG_HEADER_HEIGHT: usize = 32;
+
+const ESTIMATE_NOTE: &str =
+    "est. slot: compile-time aliasing estimate, the runtime cache may place resources differently";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourcePlacement {
+    Imported,
+    History,
+    Transient { estimated_slot: usize },
+}
+
+impl ResourcePlacement {
+    pub fn label(&self) -> String {
+        match self {
+            ResourcePlacement::Imported => "imported".to_string(),
+            ResourcePlacement::History => "history".to_string(),
+            ResourcePlacement::Transient { estimated_slot } => {
+                format!("est. slot {estimated_slot}")
+            }
+        }
+    }
+
+    fn color(&self) -> String {
+        match self {
+            ResourcePlacement::Imported => "#9e9e9e".to_string(),
+            ResourcePlacement::History => "#c9a227".to_string(),
+            ResourcePlacement::Transient { estimated_slot } => {
+                format!("hsl({}, 55%, 55%)", (estimated_slot * 67) % 360)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceLifetime {
+    pub name: String,
+    pub first_pass: usize,
+    pub last_pass: usize,
+    pub size: u64,
+    pub placement: ResourcePlacement,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ResourceLifetimeReport {
+    pub passes: Vec<String>,
+    pub resources: Vec<ResourceLifetime>,
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl ResourceLifetimeReport {
+    pub fn new(frame_graph: &FrameGraph) -> Self {
+        let passes = frame_graph
+            .execution_order
+            .iter()
+            .map(|handle| frame_graph.get_pass_node(handle).name.clone())
+            .collect::<Vec<_>>();
+
+        let mut positions = vec![None; frame_graph.pass_nodes.len()];
+        for (position, handle) in frame_graph.execution_order.iter().enumerate() {
+            positions[handle.index] = Some(position);
+        }
+
+        let mut lifetimes = vec![];
+        for resource_node in frame_graph.resource_nodes.iter() {
+            let (Some(first), Some(last)) =
+                (resource_node.first_use_pass, resource_node.last_user_pass)
+            else {
+                continue;
+            };
+            let (Some(first_pass), Some(last_pass)) =
+                (positions[first.index], positions[last.index])
+            else {
+                continue;
+            };
+
+            lifetimes.push((resource_node, first_pass, last_pass));
+        }
+
+        let mut slots = HashMap::new();
+        let mut free_slots: HashMap<&AnyTransientResourceDescriptor, Vec<usize>> = HashMap::new();
+        let mut slot_count = 0;
+
+        for position in 0..passes.len() {
+            for (resource_node, first_pass, _) in lifetimes.iter() {
+                let VirtualResource::Setuped(desc) = &resource_node.resource else {
+                    continue;
+                };
+                if *first_pass != position {
+                    continue;
+                }
+
+                let reused = if desc.is_cpu_writable() {
+                    None
+                } else {
+                    free_slots.get_mut(desc).and_then(Vec::pop)
+                };
+                let slot = reused.unwrap_or_else(|| {
+                    slot_count += 1;
+                    slot_count - 1
+                });
+
+                slots.insert(resource_node.index, slot);
+            }
+
+            for (resource_node, _, last_pass) in lifetimes.iter() {
+                let VirtualResource::Setuped(desc) = &resource_node.resource else {
+                    continue;
+                };
+                if *last_pass != position || resource_node.exported {
+                    continue;
+                }
+
+                free_slots
+                    .entry(desc)
+                    .or_default()
+                    .push(slots[&resource_node.index]);
+            }
+        }
+
+        let resources = lifetimes
+            .into_iter()
+            .map(|(resource_node, first_pass, last_pass)| {
+                let (size, placement) = match &resource_node.resource {
+                    VirtualResource::Setuped(desc) => (
+                        desc.size_in_bytes(),
+                        ResourcePlacement::Transient {
+                            estimated_slot: slots[&resource_node.index],
+                        },
+                    ),
+                    VirtualResource::Imported(resource) => (
+                        resource.get_desc().size_in_bytes(),
+                        ResourcePlacement::Imported,
+                    ),
+                    VirtualResource::History(history) => {
+                        (history.desc.size_in_bytes(), ResourcePlacement::History)
+                    }
+                };
+
+                ResourceLifetime {
+                    name: resource_node.name.clone(),
+                    first_pass,
+                    last_pass,
+                    size,
+                    placement,
+                }
+            })
+            .collect();
+
+        Self { passes, resources }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ResourceLifetime> {
+        self.resources.iter().find(|resource| resource.name == name)
+    }
+
+    pub fn estimated_slot_count(&self) -> usize {
+        self.resources
+            .iter()
+            .filter_map(|resource| match resource.placement {
+                ResourcePlacement::Transient { estimated_slot } => Some(estimated_slot + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn to_text_table(&self) -> String {
+        let mut table = String::new();
+
+        for (position, pass) in self.passes.iter().enumerate() {
+            let _ = writeln!(table, "{position:>3} {pass}");
+        }
+        table.push('\n');
+
+        let rows = self
+            .resources
+            .iter()
+            .map(|resource| {
+                (
+                    resource.name.as_str(),
+                    format_bytes(resource.size),
+                    resource.placement.label(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let name_width = rows.iter().map(|row| row.0.len()).fold(8, usize::max);
+        let size_width = rows.iter().map(|row| row.1.len()).fold(4, usize::max);
+        let slot_width = rows.iter().map(|row| row.2.len()).fold(9, usize::max);
+
+        let _ = write!(
+            table,
+            "{:<name_width$}  {:>size_width$}  {:<slot_width$}  ",
+            "resource", "size", "placement"
+        );
+        for position in 0..self.passes.len() {
+            let _ = write!(table, "{:<3}", position);
+        }
+        table.truncate(table.trim_end().len());
+        table.push('\n');
+
+        for (resource, (name, size, slot)) in self.resources.iter().zip(rows.iter()) {
+            let _ = write!(
+                table,
+                "{name:<name_width$}  {size:>size_width$}  {slot:<slot_width$}  "
+            );
+            for position in 0..self.passes.len() {
+                let cell = if (resource.first_pass..=resource.last_pass).contains(&position) {
+                    "## "
+                } else {
+                    ".  "
+                };
+                table.push_str(cell);
+            }
+            table.truncate(table.trim_end().len());
+            table.push('\n');
+        }
+
+        if self.estimated_slot_count() > 0 {
+            let _ = write!(table, "\n{ESTIMATE_NOTE}\n");
+        }
+
+        table
+    }
+
+    pub fn to_svg(&self) -> String {
+        let width = SVG_LABEL_WIDTH + self.passes.len() * SVG_PASS_WIDTH;
+        let rows_height = SVG_HEADER_HEIGHT + self.resources.len() * SVG_ROW_HEIGHT;
+        let height = rows_height + SVG_ROW_HEIGHT;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">"
+        );
+
+        for (position, pass) in self.passes.iter().enumerate() {
+            let x = SVG_LABEL_WIDTH + position * SVG_PASS_WIDTH;
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{x}\" y1=\"0\" x2=\"{x}\" y2=\"{height}\" stroke=\"#dddddd\"/>"
+            );
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"20\">{}</text>",
+                x + 4,
+                escape_xml(pass)
+            );
+        }
+
+        for (row, resource) in self.resources.iter().enumerate() {
+            let y = SVG_HEADER_HEIGHT + row * SVG_ROW_HEIGHT;
+            let x = SVG_LABEL_WIDTH + resource.first_pass * SVG_PASS_WIDTH;
+            let bar_width = (resource.last_pass - resource.first_pass + 1) * SVG_PASS_WIDTH;
+
+            let _ = writeln!(
+                svg,
+                "<text x=\"4\" y=\"{}\">{}</text>",
+                y + 16,
+                escape_xml(&resource.name)
+            );
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"3\" fill=\"{}\"/>",
+                x + 2,
+                y + 2,
+                bar_width - 4,
+                SVG_ROW_HEIGHT - 4,
+                resource.placement.color()
+            );
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\">{} | {}</text>",
+                x + 6,
+                y + 16,
+                format_bytes(resource.size),
+                resource.placement.label()
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"4\" y=\"{}\" fill=\"#666666\">{}</text>",
+            rows_height + 16,
+            escape_xml(ESTIMATE_NOTE)
+        );
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn write_svg(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_svg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::BufferUsages;
+
+    use crate::{
+        FrameGraph, PassNodeBuilderExt, ResourcePlacement, TransientBuffer,
+        TransientBufferDescriptor,
+    };
+
+    use super::{ESTIMATE_NOTE, format_bytes};
+
+    #[test]
+    fn lifetime_report_assigns_aliased_slots_in_execution_order() {
+        let desc = TransientBufferDescriptor {
+            label: None,
+            size: 2048,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        };
+
+        let mut frame_graph = FrameGraph::default();
+
+        let a = frame_graph.create("a", desc.clone());
+        let b = frame_graph.create("b", desc.clone());
+        let c = frame_graph.create("c", desc);
+        frame_graph.mark_output(&c);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("first");
+            builder.write(a.clone());
+        }
+
+        let a = frame_graph.get_resource_node(&a.raw.index).get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("second");
+            builder.read::<TransientBuffer>(a);
+            builder.write(b.clone());
+        }
+
+        let b = frame_graph.get_resource_node(&b.raw.index).get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("third");
+            builder.read::<TransientBuffer>(b);
+            builder.write(c);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let report = frame_graph.lifetime_report();
+        assert_eq!(report.passes, vec!["first", "second", "third"]);
+
+        let placements = report
+            .resources
+            .iter()
+            .map(|resource| (resource.first_pass, resource.last_pass, resource.placement))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            placements,
+            vec![
+                (0, 1, ResourcePlacement::Transient { estimated_slot: 0 }),
+                (1, 2, ResourcePlacement::Transient { estimated_slot: 1 }),
+                (2, 2, ResourcePlacement::Transient { estimated_slot: 0 }),
+            ]
+        );
+        assert_eq!(report.estimated_slot_count(), 2);
+
+        let table = report.to_text_table();
+        assert!(table.contains("a         2.0 KiB  est. slot 0  ## ##"));
+        assert!(table.contains("c         2.0 KiB  est. slot 0  .  .  ##"));
+        assert!(table.ends_with(&format!("{ESTIMATE_NOTE}\n")));
+
+        let svg = report.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("compile-time aliasing estimate"));
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -1,48 +1,178 @@
+mod compile_error;
 mod device_pass;
+mod error_scope;
+mod lifetime_report;
 mod pass_node_builder;
+mod profiler;
+mod snapshot;
+mod stats;
+mod trace;
 
+pub use compile_error::*;
 pub use device_pass::*;
+pub use error_scope::*;
+pub use lifetime_report::*;
 pub use pass_node_builder::*;
+pub use profiler::*;
+pub use snapshot::*;
+pub use stats::*;
+pub use trace::*;
 
-use std::sync::Arc;
-use wgpu::{CommandBuffer, Device};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+use wgpu::{
+    Buffer, CommandBuffer, Device, Extent3d, Queue, SubmissionIndex, Texture, TextureFormat,
+    TextureView,
+};
 
 use crate::{
-    IndexHandle, IntoArcAnyTransientResource, PassNode, PipelineContainer, ResourceBoard,
-    ResourceHandle, ResourceNode, ResourceTable, TransientResource, TransientResourceCache,
-    TransientResourceDescriptor, TypeEquals, VirtualResource,
+    AnyTransientResourceDescriptor, CreatableResourceDescriptor, FrameCapture,
+    HistoryResourceDescriptor, HistoryResourceHandle, HistorySlot, ImportedTextureView,
+    ImportedTextureViewDescriptor, IndexHandle, IntoArcAnyTransientResource, PassNode,
+    PipelineContainer, RawResourceHandle, RelativeSize, RelativeSizeError, RelativeSizeSource,
+    RelativeTextureDescriptor, ResourceBoard, ResourceHandle, ResourceNode, ResourceRange,
+    ResourceTable, TransientBuffer, TransientBufferDescriptor, TransientResource,
+    TransientResourceCache, TransientResourceCreator, TransientResourceDescriptor,
+    TransientTexture, TransientTextureDescriptor, TypeEquals, UniformAllocator, VirtualResource,
 };
 
 pub struct FrameGraphContext<'a> {
     pub resource_table: ResourceTable,
     pub pipeline_container: PipelineContainer,
-    pub device: Device,
     pub transient_resource_cache: &'a mut TransientResourceCache,
+    pub resource_creator: Box<dyn TransientResourceCreator + 'a>,
     pub(crate) command_buffers: Vec<CommandBuffer>,
+    pub(crate) stats: FrameGraphStats,
+    pub(crate) error_scopes: bool,
+    pub(crate) pass_errors: Vec<PassError>,
+    pending_pass_errors: Vec<PendingPassError>,
 }
 
 impl<'a> FrameGraphContext<'a> {
     pub fn new(
         pipeline_container: PipelineContainer,
-        device: &'a Device,
+        resource_creator: impl TransientResourceCreator + 'a,
         transient_resource_cache: &'a mut TransientResourceCache,
     ) -> Self {
+        transient_resource_cache.track_deferred_submission();
+
         Self {
             resource_table: Default::default(),
             pipeline_container,
-            device: device.clone(),
             transient_resource_cache,
+            resource_creator: Box::new(resource_creator),
             command_buffers: vec![],
+            stats: FrameGraphStats::default(),
+            error_scopes: false,
+            pass_errors: vec![],
+            pending_pass_errors: vec![],
         }
     }
 
+    pub fn device(&self) -> &Device {
+        self.resource_creator.device()
+    }
+
+    pub fn with_error_scopes(mut self, error_scopes: bool) -> Self {
+        self.error_scopes = error_scopes;
+        self
+    }
+
+    pub fn pass_errors(&self) -> &[PassError] {
+        &self.pass_errors
+    }
+
+    pub fn take_pass_errors(&mut self) -> Vec<PassError> {
+        self.poll_pass_errors();
+
+        std::mem::take(&mut self.pass_errors)
+    }
+
+    pub fn has_pending_pass_errors(&self) -> bool {
+        !self.pending_pass_errors.is_empty()
+    }
+
+    pub fn poll_pass_errors(&mut self) {
+        let mut context = Context::from_waker(Waker::noop());
+        let pass_errors = &mut self.pass_errors;
+
+        self.pending_pass_errors
+            .retain_mut(|pending| match Pin::new(pending).poll(&mut context) {
+                Poll::Ready(errors) => {
+                    pass_errors.extend(errors);
+                    false
+                }
+                Poll::Pending => true,
+            });
+    }
+
+    pub async fn resolve_pass_errors(&mut self) -> Vec<PassError> {
+        for pending in std::mem::take(&mut self.pending_pass_errors) {
+            let errors = pending.await;
+            self.pass_errors.extend(errors);
+        }
+
+        std::mem::take(&mut self.pass_errors)
+    }
+
+    pub(crate) fn push_pending_pass_error(&mut self, pending: PendingPassError) {
+        self.pending_pass_errors.push(pending);
+        self.poll_pass_errors();
+    }
+
+    pub fn with_debug_fill(mut self, debug_fill: bool) -> Self {
+        self.resource_table
+            .set_debug_fill(debug_fill.then_some(self.resource_creator.device()));
+        self
+    }
+
+    pub fn take_exported<ResourceType: IntoArcAnyTransientResource>(
+        &mut self,
+        handle: &ResourceHandle<ResourceType>,
+    ) -> Option<Arc<ResourceType>> {
+        self.resource_table
+            .take_exported_resource(&handle.raw.index)
+    }
+
+    pub fn frame_index(&self) -> u64 {
+        self.transient_resource_cache.frame_index()
+    }
+
+    pub fn frames_in_flight(&self) -> u32 {
+        self.transient_resource_cache.frames_in_flight()
+    }
+
+    pub fn stats(&self) -> &FrameGraphStats {
+        &self.stats
+    }
+
     pub fn add_command_buffer(&mut self, command_buffer: CommandBuffer) {
         self.command_buffers.push(command_buffer);
     }
 
-    pub fn finish(self) -> Vec<CommandBuffer> {
+    pub fn finish(self, queue: &Queue) -> Vec<CommandBuffer> {
+        self.transient_resource_cache.end_frame();
+        self.transient_resource_cache
+            .defer_submission_tracking(queue);
+
         self.command_buffers
     }
+
+    pub fn submit(self, queue: &Queue) -> SubmissionIndex {
+        let transient_resource_cache = self.transient_resource_cache;
+        transient_resource_cache.end_frame();
+
+        let submission = queue.submit(self.command_buffers);
+        transient_resource_cache.track_submission(queue);
+
+        submission
+    }
 }
 
 pub trait ResourceMaterial {
@@ -53,13 +183,120 @@ pub trait ResourceMaterial {
 
 pub struct CompiledFrameGraph {
     device_passes: Vec<DevicePass>,
+    passes_declared: usize,
+    passes_culled: usize,
+    profiling: bool,
+    profile_report: Option<FrameProfileReport>,
+    trace: Option<FrameTrace>,
+    frame_capture: Option<FrameCapture>,
 }
 
 impl CompiledFrameGraph {
-    pub fn execute(&self, context: &mut FrameGraphContext) {
+    pub fn execute(&mut self, context: &mut FrameGraphContext) {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("frame_graph_execute", pass_count = self.device_passes.len())
+                .entered();
+
+        if let Some(frame_capture) = &mut self.frame_capture {
+            frame_capture.contents.encode_readback(context);
+        }
+
+        context.stats = FrameGraphStats::new(self.passes_declared, self.passes_culled);
+        let bind_groups_created = context
+            .transient_resource_cache
+            .bind_group_cache()
+            .created_count();
+        let texture_views_created = context
+            .transient_resource_cache
+            .texture_view_cache()
+            .created_count();
+
+        self.execute_passes(context);
+
+        context.stats.bind_groups_created = context
+            .transient_resource_cache
+            .bind_group_cache()
+            .created_count()
+            - bind_groups_created;
+        context.stats.texture_views_created = context
+            .transient_resource_cache
+            .texture_view_cache()
+            .created_count()
+            - texture_views_created;
+    }
+
+    fn execute_passes(&mut self, context: &mut FrameGraphContext) {
+        let mut profiler = self
+            .profiling
+            .then(|| FrameProfiler::new(context.device(), self.device_passes.len()));
+
         for device_pass in self.device_passes.iter() {
-            device_pass.execute(context);
+            if let Some(profiler) = &profiler {
+                profiler.begin_pass(context);
+            }
+
+            let timings = device_pass.execute(context);
+
+            if let Some(profiler) = &profiler {
+                profiler.end_pass(context);
+            }
+
+            if let Some(trace) = &mut self.trace {
+                trace.add_span(
+                    &device_pass.name,
+                    "pass",
+                    timings.request_start,
+                    timings.total(),
+                );
+                trace.add_span("request", "pass", timings.request_start, timings.request);
+                for acquisition in timings.acquisitions.iter() {
+                    trace.add_acquisition(
+                        &acquisition.resource,
+                        acquisition.acquisition,
+                        acquisition.start,
+                    );
+                }
+                trace.add_span("record", "pass", timings.record_start, timings.record);
+                trace.add_span("release", "pass", timings.release_start, timings.release);
+            }
+
+            if let Some(profiler) = &mut profiler {
+                profiler.push(PassProfile {
+                    name: device_pass.name.clone(),
+                    request: timings.request,
+                    record: timings.record,
+                    release: timings.release,
+                    gpu: None,
+                });
+            }
         }
+
+        self.profile_report = profiler.map(|profiler| profiler.finish(context));
+    }
+
+    pub fn trace(&self) -> Option<&FrameTrace> {
+        self.trace.as_ref()
+    }
+
+    pub fn take_trace(&mut self) -> Option<FrameTrace> {
+        self.trace.take()
+    }
+
+    pub fn frame_capture(&self) -> Option<&FrameCapture> {
+        self.frame_capture.as_ref()
+    }
+
+    pub fn take_frame_capture(&mut self) -> Option<FrameCapture> {
+        self.frame_capture.take()
+    }
+
+    pub fn profile_report(&self) -> Option<&FrameProfileReport> {
+        self.profile_report.as_ref()
+    }
+
+    pub fn take_profile_report(&mut self) -> Option<FrameProfileReport> {
+        self.profile_report.take()
     }
 }
 
@@ -69,14 +306,28 @@ pub struct FrameGraph {
     pub(crate) pass_nodes: Vec<PassNode>,
     pub(crate) compiled_frame_graph: Option<CompiledFrameGraph>,
     pub(crate) resource_board: ResourceBoard,
+    pub(crate) reference_resolution: Extent3d,
+    pub(crate) uniform_allocator: UniformAllocator,
+    pub(crate) execution_order: Vec<IndexHandle<PassNode>>,
+    pub(crate) profiling: bool,
+    pub(crate) profile_report: Option<FrameProfileReport>,
+    pub(crate) trace_capture: bool,
+    pub(crate) trace: Option<FrameTrace>,
+    pub(crate) frame_capture_enabled: bool,
+    pub(crate) capture_imported_contents: bool,
+    pub(crate) frame_capture: Option<FrameCapture>,
+    pub(crate) builder_errors: Vec<CompileError>,
 }
 
 impl FrameGraph {
     pub fn reset(&mut self) {
         self.pass_nodes = vec![];
+        self.builder_errors = vec![];
         self.resource_nodes = vec![];
         self.compiled_frame_graph = None;
         self.resource_board = ResourceBoard::default();
+        self.uniform_allocator.reset();
+        self.execution_order = vec![];
     }
 
     pub fn execute(&mut self, context: &mut FrameGraphContext) {
@@ -86,13 +337,327 @@ impl FrameGraph {
 
         if let Some(compiled_frame_graph) = &mut self.compiled_frame_graph {
             compiled_frame_graph.execute(context);
+            self.profile_report = compiled_frame_graph.take_profile_report();
+            self.trace = compiled_frame_graph.take_trace();
+            self.frame_capture = compiled_frame_graph.take_frame_capture();
         }
 
         self.reset();
     }
 
-    fn compute_resource_lifetime(&mut self) {
+    pub fn set_trace_capture(&mut self, trace_capture: bool) {
+        self.trace_capture = trace_capture;
+    }
+
+    pub fn trace(&self) -> Option<&FrameTrace> {
+        self.trace.as_ref()
+    }
+
+    pub fn take_trace(&mut self) -> Option<FrameTrace> {
+        self.trace.take()
+    }
+
+    pub fn set_frame_capture(&mut self, frame_capture: bool) {
+        self.frame_capture_enabled = frame_capture;
+    }
+
+    pub fn set_capture_imported_contents(&mut self, capture_imported_contents: bool) {
+        self.capture_imported_contents = capture_imported_contents;
+    }
+
+    pub fn frame_capture(&self) -> Option<&FrameCapture> {
+        self.frame_capture.as_ref()
+    }
+
+    pub fn take_frame_capture(&mut self) -> Option<FrameCapture> {
+        self.frame_capture.take()
+    }
+
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    pub fn profile_report(&self) -> Option<&FrameProfileReport> {
+        self.profile_report.as_ref()
+    }
+
+    pub fn take_profile_report(&mut self) -> Option<FrameProfileReport> {
+        self.profile_report.take()
+    }
+
+    pub fn set_reference_resolution(&mut self, width: u32, height: u32) {
+        self.reference_resolution = Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+    }
+
+    pub fn reference_resolution(&self) -> Extent3d {
+        self.reference_resolution
+    }
+
+    fn resolve_relative_size(
+        &self,
+        relative_size: &RelativeSize,
+        depth_or_array_layers: u32,
+    ) -> Extent3d {
+        let source_size = match relative_size.source {
+            RelativeSizeSource::Reference => Some(self.reference_resolution),
+            RelativeSizeSource::Resource(index) => self
+                .get_resource_node(&index)
+                .resource
+                .get_any_desc()
+                .texture_size(),
+        };
+
+        relative_size.resolve(
+            source_size.unwrap_or(self.reference_resolution),
+            depth_or_array_layers,
+        )
+    }
+
+    fn resolve_relative_sizes(&mut self) {
+        for index in 0..self.resource_nodes.len() {
+            let resource_node = &self.resource_nodes[index];
+
+            let Some(relative_size) = resource_node.relative_size else {
+                continue;
+            };
+
+            let VirtualResource::Setuped(AnyTransientResourceDescriptor::Texture(desc)) =
+                &resource_node.resource
+            else {
+                continue;
+            };
+
+            let size = self.resolve_relative_size(&relative_size, desc.size.depth_or_array_layers);
+
+            if let VirtualResource::Setuped(AnyTransientResourceDescriptor::Texture(desc)) =
+                &mut self.resource_nodes[index].resource
+            {
+                desc.size = size;
+            }
+        }
+    }
+
+    pub fn execution_order(&self) -> &[IndexHandle<PassNode>] {
+        &self.execution_order
+    }
+
+    pub fn lifetime_report(&self) -> ResourceLifetimeReport {
+        ResourceLifetimeReport::new(self)
+    }
+
+    pub fn snapshot(&self) -> FrameGraphSnapshot {
+        FrameGraphSnapshot::new(self)
+    }
+
+    fn get_producers(&self) -> HashMap<RawResourceHandle, IndexHandle<PassNode>> {
+        let mut producers = HashMap::new();
+        for pass_node in self.pass_nodes.iter() {
+            for resource_handle in pass_node.writes.iter() {
+                producers.insert(resource_handle.clone(), pass_node.index);
+            }
+        }
+
+        producers
+    }
+
+    fn resolve_dependencies(&self) -> Result<Vec<Vec<IndexHandle<PassNode>>>, CompileError> {
+        self.pass_nodes
+            .iter()
+            .map(|pass_node| {
+                let mut dependencies = vec![];
+
+                for name in pass_node.dependencies.iter() {
+                    let count = dependencies.len();
+                    dependencies.extend(
+                        self.pass_nodes
+                            .iter()
+                            .filter(|other| other.name == *name && other.index != pass_node.index)
+                            .map(|other| other.index),
+                    );
+
+                    if dependencies.len() == count {
+                        return Err(CompileError::UnknownDependency {
+                            pass: pass_node.name.clone(),
+                            dependency: name.clone(),
+                        });
+                    }
+                }
+
+                Ok(dependencies)
+            })
+            .collect()
+    }
+
+    fn get_write_range(&self, resource_handle: &RawResourceHandle) -> Option<&ResourceRange> {
+        self.get_resource_node(&resource_handle.index)
+            .range_writes
+            .iter()
+            .find(|write| write.version == resource_handle.version)
+            .and_then(|write| write.range.as_ref())
+    }
+
+    fn ranges_overlap(range: Option<&ResourceRange>, other: Option<&ResourceRange>) -> bool {
+        match (range, other) {
+            (Some(range), Some(other)) => range.overlaps(other),
+            _ => true,
+        }
+    }
+
+    fn sort_passes(
+        &mut self,
+        dependencies: &[Vec<IndexHandle<PassNode>>],
+    ) -> Result<(), CompileError> {
+        let pass_count = self.pass_nodes.len();
+        let producers = self.get_producers();
+
+        let mut predecessors = vec![HashSet::new(); pass_count];
+
+        for pass_node in self.pass_nodes.iter().filter(|pass_node| !pass_node.culled) {
+            let pass_index = pass_node.index.index;
+
+            for dependency in dependencies[pass_index].iter() {
+                predecessors[pass_index].insert(dependency.index);
+            }
+
+            for resource_handle in pass_node.reads.iter() {
+                if let Some(producer) = producers.get(resource_handle) {
+                    predecessors[pass_index].insert(producer.index);
+                }
+            }
+
+            for resource_handle in pass_node.writes.iter() {
+                let range = self.get_write_range(resource_handle);
+
+                for other in self.pass_nodes.iter() {
+                    if other.index == pass_node.index {
+                        continue;
+                    }
+
+                    let writes_before = other.writes.iter().any(|other_handle| {
+                        other_handle.index == resource_handle.index
+                            && other_handle.version < resource_handle.version
+                            && Self::ranges_overlap(range, self.get_write_range(other_handle))
+                    });
+
+                    let reads_before = other
+                        .reads
+                        .iter()
+                        .filter(|other_handle| other_handle.index == resource_handle.index)
+                        .map(|other_handle| other_handle.version)
+                        .max()
+                        .is_some_and(|version| version < resource_handle.version)
+                        && other.read_ranges.iter().any(|(index, read_range)| {
+                            *index == resource_handle.index
+                                && Self::ranges_overlap(range, read_range.as_ref())
+                        });
+
+                    if writes_before || reads_before {
+                        predecessors[pass_index].insert(other.index.index);
+                    }
+                }
+            }
+        }
+
+        let mut in_degree = vec![0; pass_count];
+        let mut successors = vec![vec![]; pass_count];
+
+        for (pass_index, pass_predecessors) in predecessors.iter().enumerate() {
+            for predecessor in pass_predecessors.iter() {
+                if self.pass_nodes[*predecessor].culled {
+                    continue;
+                }
+
+                in_degree[pass_index] += 1;
+                successors[*predecessor].push(pass_index);
+            }
+        }
+
+        let mut ready = self
+            .pass_nodes
+            .iter()
+            .filter(|pass_node| !pass_node.culled && in_degree[pass_node.index.index] == 0)
+            .map(|pass_node| Reverse(pass_node.index.index))
+            .collect::<BinaryHeap<_>>();
+
+        let mut execution_order = vec![];
+
+        while let Some(Reverse(pass_index)) = ready.pop() {
+            execution_order.push(IndexHandle::new(pass_index));
+
+            for successor in successors[pass_index].iter() {
+                in_degree[*successor] -= 1;
+                if in_degree[*successor] == 0 {
+                    ready.push(Reverse(*successor));
+                }
+            }
+        }
+
+        let active_count = self
+            .pass_nodes
+            .iter()
+            .filter(|pass_node| !pass_node.culled)
+            .count();
+
+        if execution_order.len() != active_count {
+            let passes = self
+                .pass_nodes
+                .iter()
+                .filter(|pass_node| !pass_node.culled && in_degree[pass_node.index.index] > 0)
+                .map(|pass_node| pass_node.name.clone())
+                .collect();
+
+            return Err(CompileError::CyclicDependencies { passes });
+        }
+
+        self.execution_order = execution_order;
+
+        Ok(())
+    }
+
+    fn cull(&mut self, dependencies: &[Vec<IndexHandle<PassNode>>]) {
+        let producers = self.get_producers();
+
+        let mut stack = vec![];
         for pass_node in self.pass_nodes.iter_mut() {
+            let is_root = pass_node.writes.is_empty()
+                || pass_node.writes.iter().any(|resource_handle| {
+                    self.resource_nodes[resource_handle.index.index].is_output()
+                });
+
+            pass_node.culled = !is_root;
+            if is_root {
+                stack.push(pass_node.index);
+            }
+        }
+
+        while let Some(pass_node_handle) = stack.pop() {
+            let resource_producers = self.pass_nodes[pass_node_handle.index]
+                .reads
+                .iter()
+                .filter_map(|resource_handle| producers.get(resource_handle).copied())
+                .collect::<Vec<_>>();
+
+            for producer in resource_producers
+                .into_iter()
+                .chain(dependencies[pass_node_handle.index].iter().copied())
+            {
+                let producer_node = &mut self.pass_nodes[producer.index];
+                if producer_node.culled {
+                    producer_node.culled = false;
+                    stack.push(producer);
+                }
+            }
+        }
+    }
+
+    fn compute_resource_lifetime(&mut self) {
+        for pass_node_handle in self.execution_order.iter() {
+            let pass_node = &self.pass_nodes[pass_node_handle.index];
+
             for resource_handle in pass_node.reads.iter() {
                 let resource_node = &mut self.resource_nodes[resource_handle.index.index];
                 resource_node.update_lifetime(pass_node.index);
@@ -125,6 +690,18 @@ impl FrameGraph {
         }
     }
 
+    fn validate_exports(&self) -> Result<(), CompileError> {
+        for resource_node in self.resource_nodes.iter() {
+            if resource_node.exported && resource_node.first_use_pass.is_none() {
+                return Err(CompileError::UnusedExport {
+                    resource: resource_node.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn generate_compiled_frame_graph(&mut self) {
         if self.pass_nodes.is_empty() {
             return;
@@ -132,26 +709,70 @@ impl FrameGraph {
 
         let mut device_passes = vec![];
 
-        for index in 0..self.pass_nodes.len() {
-            let type_index = self.pass_nodes[index].index;
-
+        for index in self.execution_order.clone() {
             let mut device_pass = DevicePass::default();
-            device_pass.extra(self, type_index);
+            device_pass.extra(self, index);
 
             device_passes.push(device_pass);
         }
 
-        self.compiled_frame_graph = Some(CompiledFrameGraph { device_passes });
+        let passes_culled = self
+            .pass_nodes
+            .iter()
+            .filter(|pass_node| pass_node.culled)
+            .count();
+
+        self.compiled_frame_graph = Some(CompiledFrameGraph {
+            device_passes,
+            passes_declared: self.pass_nodes.len(),
+            passes_culled,
+            profiling: self.profiling,
+            profile_report: None,
+            trace: None,
+            frame_capture: None,
+        });
     }
 
-    pub fn compile(&mut self) {
+    pub fn compile(&mut self) -> Result<(), CompileError> {
+        if let Some(error) = self.builder_errors.first() {
+            return Err(error.clone());
+        }
+
         if self.pass_nodes.is_empty() {
-            return;
+            return Ok(());
         }
-        //todo cull
 
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("frame_graph_compile", pass_count = self.pass_nodes.len())
+            .entered();
+
+        let mut trace = self.trace_capture.then(FrameTrace::default);
+        let compile_start = Instant::now();
+
+        let dependencies = self.resolve_dependencies()?;
+
+        self.resolve_relative_sizes();
+        self.cull(&dependencies);
+        self.sort_passes(&dependencies)?;
         self.compute_resource_lifetime();
+        self.validate_exports()?;
+
+        let frame_capture = self
+            .frame_capture_enabled
+            .then(|| FrameCapture::new(self, self.capture_imported_contents));
+
         self.generate_compiled_frame_graph();
+
+        if let Some(trace) = &mut trace {
+            trace.add_span("compile", "compile", compile_start, compile_start.elapsed());
+        }
+
+        if let Some(compiled_frame_graph) = &mut self.compiled_frame_graph {
+            compiled_frame_graph.trace = trace;
+            compiled_frame_graph.frame_capture = frame_capture;
+        }
+
+        Ok(())
     }
 }
 
@@ -207,6 +828,20 @@ impl FrameGraph {
         &self.resource_nodes[handle.index]
     }
 
+    pub fn mark_output<ResourceType: TransientResource>(
+        &mut self,
+        handle: &ResourceHandle<ResourceType>,
+    ) {
+        self.get_resource_node_mut(&handle.raw.index).output = true;
+    }
+
+    pub fn export<ResourceType: TransientResource>(
+        &mut self,
+        handle: &ResourceHandle<ResourceType>,
+    ) {
+        self.get_resource_node_mut(&handle.raw.index).exported = true;
+    }
+
     pub fn import<ResourceType>(
         &mut self,
         name: &str,
@@ -235,9 +870,63 @@ impl FrameGraph {
         handle
     }
 
+    pub fn import_texture(
+        &mut self,
+        name: &str,
+        texture: Texture,
+    ) -> ResourceHandle<TransientTexture> {
+        let desc = TransientTextureDescriptor {
+            label: Some(name.to_string()),
+            ..TransientTextureDescriptor::from_texture(&texture)
+        };
+
+        self.import(
+            name,
+            Arc::new(TransientTexture {
+                resource: texture,
+                desc,
+            }),
+        )
+    }
+
+    pub fn import_buffer(&mut self, name: &str, buffer: Buffer) -> ResourceHandle<TransientBuffer> {
+        let desc = TransientBufferDescriptor {
+            label: Some(name.to_string()),
+            ..TransientBufferDescriptor::from_buffer(&buffer)
+        };
+
+        self.import(
+            name,
+            Arc::new(TransientBuffer {
+                resource: buffer,
+                desc,
+            }),
+        )
+    }
+
+    pub fn import_texture_view(
+        &mut self,
+        name: &str,
+        texture_view: TextureView,
+        format: TextureFormat,
+        size: Extent3d,
+    ) -> ResourceHandle<ImportedTextureView> {
+        self.import(
+            name,
+            Arc::new(ImportedTextureView {
+                resource: texture_view,
+                desc: ImportedTextureViewDescriptor {
+                    label: Some(name.to_string()),
+                    format,
+                    size,
+                },
+            }),
+        )
+    }
+
     pub fn get_or_create<DescriptorType>(&mut self, name: &str, desc: DescriptorType) -> ResourceHandle<DescriptorType::Resource>
     where
-        DescriptorType: TransientResourceDescriptor
+        DescriptorType: CreatableResourceDescriptor
             + TypeEquals<
                 Other = <<DescriptorType as TransientResourceDescriptor>::Resource as TransientResource>::Descriptor,
             >,
@@ -256,13 +945,71 @@ impl FrameGraph {
 
     pub fn create<DescriptorType>(&mut self, name: &str, desc: DescriptorType) -> ResourceHandle<DescriptorType::Resource>
     where
-        DescriptorType: TransientResourceDescriptor
+        DescriptorType: CreatableResourceDescriptor
+            + TypeEquals<
+                Other = <<DescriptorType as TransientResourceDescriptor>::Resource as TransientResource>::Descriptor,
+            >,
+    {
+        self.create_resource_node(name, VirtualResource::Setuped(desc.into()))
+    }
+
+    pub fn create_relative(
+        &mut self,
+        name: &str,
+        desc: RelativeTextureDescriptor,
+    ) -> Result<ResourceHandle<TransientTexture>, RelativeSizeError> {
+        if let RelativeSizeSource::Resource(index) = desc.size.source {
+            let source = self.get_resource_node(&index);
+
+            if source.resource.get_any_desc().texture_size().is_none() {
+                return Err(RelativeSizeError::SourceNotTexture {
+                    source: source.name.clone(),
+                });
+            }
+        }
+
+        let handle = self.create(name, desc.desc);
+        self.get_resource_node_mut(&handle.raw.index).relative_size = Some(desc.size);
+
+        Ok(handle)
+    }
+
+    pub fn create_history<DescriptorType>(&mut self, key: &str, desc: DescriptorType) -> HistoryResourceHandle<DescriptorType::Resource>
+    where
+        DescriptorType: CreatableResourceDescriptor
             + TypeEquals<
                 Other = <<DescriptorType as TransientResourceDescriptor>::Resource as TransientResource>::Descriptor,
             >,
     {
+        let desc = desc.into();
+
+        let previous = self.create_resource_node(
+            &format!("{key}_previous"),
+            VirtualResource::History(HistoryResourceDescriptor {
+                key: key.to_string(),
+                desc: desc.clone(),
+                slot: HistorySlot::Previous,
+            }),
+        );
+
+        let current = self.create_resource_node(
+            &format!("{key}_current"),
+            VirtualResource::History(HistoryResourceDescriptor {
+                key: key.to_string(),
+                desc,
+                slot: HistorySlot::Current,
+            }),
+        );
+
+        HistoryResourceHandle { previous, current }
+    }
+
+    pub(crate) fn create_resource_node<ResourceType: TransientResource>(
+        &mut self,
+        name: &str,
+        virtual_resource: VirtualResource,
+    ) -> ResourceHandle<ResourceType> {
         let resource_node_handle = IndexHandle::new(self.resource_nodes.len());
-        let virtual_resource = VirtualResource::Setuped(desc.into());
         let resource_node = ResourceNode::new(name, resource_node_handle, virtual_resource);
 
         let handle = resource_node.get_handle();
@@ -272,3 +1019,981 @@ impl FrameGraph {
         handle
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{
+        BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
+        BufferUsages, Color, Extent3d, LoadOp, Operations, PollType, ShaderStages, StoreOp,
+        TextureDimension, TextureFormat, TextureUsages,
+    };
+
+    use crate::{
+        AnyTransientResourceDescriptor, CompileError, FrameGraph, FrameGraphContext,
+        ImportedTextureView, ImportedTextureViewDescriptor, IndexHandle, PassCommand, PassContext,
+        PassNodeBuilderExt, PipelineContainer, RecordingResourceCreator, RelativeSize,
+        RelativeSizeError, RelativeTextureDescriptor, ResourceCreatorRecord, SizeRounding,
+        TextureViewEdge, TraceEventKind, TransientBindGroup, TransientBindGroupBuffer,
+        TransientBindGroupEntry, TransientBindGroupResource, TransientBuffer,
+        TransientBufferDescriptor, TransientRenderPassColorAttachment, TransientResourceCache,
+        TransientResourceCreator, TransientTexture, TransientTextureDescriptor,
+        TransientTextureViewDescriptor,
+    };
+
+    #[test]
+    fn frame_graph_context_finish_tracks_submission_for_cpu_writable_reuse() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let desc = AnyTransientResourceDescriptor::from(TransientBufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        transient_resource_cache.insert_resource(desc.clone(), device.create_resource(&desc));
+
+        let context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        queue.submit(context.finish(&queue));
+        assert!(!transient_resource_cache.is_frame_completed(0));
+
+        let context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        device.poll(PollType::wait_indefinitely()).unwrap();
+        assert!(context.transient_resource_cache.is_frame_completed(0));
+        assert!(
+            context
+                .transient_resource_cache
+                .get_resource(&desc)
+                .is_some()
+        );
+        queue.submit(context.finish(&queue));
+    }
+
+    fn buffer_desc() -> TransientBufferDescriptor {
+        TransientBufferDescriptor {
+            label: None,
+            size: 256,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        }
+    }
+
+    #[test]
+    fn frame_graph_compile_culls_passes_not_contributing_to_outputs() {
+        let mut frame_graph = FrameGraph::default();
+
+        let intermediate = frame_graph.get_or_create("intermediate", buffer_desc());
+        let unused = frame_graph.create("unused", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("producer");
+            builder.write(intermediate.clone());
+        }
+
+        let intermediate = frame_graph.get::<TransientBuffer>("intermediate").unwrap();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("consumer");
+            builder.read(intermediate);
+            builder.write(output);
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("dead");
+            builder.write(unused);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let culled = (0..3)
+            .map(|index| frame_graph.get_pass_node(&IndexHandle::new(index)).culled)
+            .collect::<Vec<_>>();
+        assert_eq!(culled, vec![false, false, true]);
+
+        let unused_node = frame_graph.get_resource_node(&IndexHandle::new(1));
+        assert!(unused_node.first_use_pass.is_none());
+    }
+
+    #[test]
+    fn frame_graph_compile_keeps_passes_without_declared_writes() {
+        let mut frame_graph = FrameGraph::default();
+
+        let input = frame_graph.create("input", buffer_desc());
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("side_effect");
+            builder.read(input);
+        }
+
+        frame_graph.compile().unwrap();
+
+        assert!(!frame_graph.get_pass_node(&IndexHandle::new(0)).culled);
+    }
+
+    #[test]
+    fn frame_graph_compile_resolves_relative_texture_sizes() {
+        let mut frame_graph = FrameGraph::default();
+
+        let texture_desc = TransientTextureDescriptor {
+            label: None,
+            size: Extent3d::default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: vec![],
+        };
+
+        let half = frame_graph
+            .create_relative(
+                "half",
+                RelativeTextureDescriptor {
+                    desc: texture_desc.clone(),
+                    size: RelativeSize::reference(0.5),
+                },
+            )
+            .unwrap();
+        let quarter = frame_graph
+            .create_relative(
+                "quarter",
+                RelativeTextureDescriptor {
+                    desc: texture_desc,
+                    size: RelativeSize::resource(half.raw.index, 0.5)
+                        .with_rounding(SizeRounding::Floor),
+                },
+            )
+            .unwrap();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("downsample");
+            builder.write(quarter.clone());
+        }
+
+        assert_eq!(
+            frame_graph
+                .get_resource_node(&quarter.raw.index)
+                .get_desc::<TransientTexture>()
+                .size,
+            Extent3d::default()
+        );
+
+        frame_graph.set_reference_resolution(1919, 1080);
+        frame_graph.compile().unwrap();
+
+        let half_size = frame_graph
+            .get_resource_node(&half.raw.index)
+            .get_desc::<TransientTexture>()
+            .size;
+        assert_eq!((half_size.width, half_size.height), (960, 540));
+
+        let quarter_size = frame_graph
+            .get_resource_node(&quarter.raw.index)
+            .get_desc::<TransientTexture>()
+            .size;
+        assert_eq!((quarter_size.width, quarter_size.height), (480, 270));
+    }
+
+    #[test]
+    fn frame_graph_create_relative_rejects_buffer_sources() {
+        let mut frame_graph = FrameGraph::default();
+
+        let buffer = frame_graph.create("buffer", buffer_desc());
+        let result = frame_graph.create_relative(
+            "relative",
+            RelativeTextureDescriptor {
+                desc: TransientTextureDescriptor {
+                    label: None,
+                    size: Extent3d::default(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba8Unorm,
+                    usage: TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: vec![],
+                },
+                size: RelativeSize::resource(buffer.raw.index, 0.5),
+            },
+        );
+
+        assert_eq!(
+            result.err(),
+            Some(RelativeSizeError::SourceNotTexture {
+                source: "buffer".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn frame_graph_tracks_dependencies_per_subresource() {
+        let mut frame_graph = FrameGraph::default();
+
+        let cubemap = frame_graph.create(
+            "cubemap",
+            TransientTextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 64,
+                    height: 64,
+                    depth_or_array_layers: 6,
+                },
+                mip_level_count: 2,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                view_formats: vec![],
+            },
+        );
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        for face in 0..6 {
+            let mut builder = frame_graph.create_pass_node_builder(&format!("face_{face}"));
+            builder.write_subresource(
+                cubemap.clone(),
+                &TransientTextureViewDescriptor {
+                    mip_level_count: Some(1),
+                    base_array_layer: face,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let mip_0 = TransientTextureViewDescriptor {
+            mip_level_count: Some(1),
+            ..Default::default()
+        };
+        let mip_1 = TransientTextureViewDescriptor {
+            base_mip_level: 1,
+            mip_level_count: Some(1),
+            ..Default::default()
+        };
+
+        let cubemap = frame_graph
+            .get_resource_node(&cubemap.raw.index)
+            .get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("downsample");
+            builder.read_subresource(cubemap.clone(), &mip_0);
+            builder.write_subresource(cubemap.clone(), &mip_1);
+        }
+
+        let cubemap = frame_graph
+            .get_resource_node(&cubemap.raw.index)
+            .get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("consumer");
+            builder.read_subresource(cubemap, &mip_1);
+            builder.write(output);
+        }
+
+        let downsample = frame_graph.get_pass_node(&IndexHandle::new(6));
+        assert_eq!(downsample.reads.len(), 6);
+
+        let consumer = frame_graph.get_pass_node(&IndexHandle::new(7));
+        assert_eq!(consumer.reads.len(), 1);
+        assert_eq!(consumer.reads[0].version, 7);
+
+        frame_graph.compile().unwrap();
+
+        assert!((0..8).all(|index| !frame_graph.get_pass_node(&IndexHandle::new(index)).culled));
+    }
+
+    #[test]
+    fn frame_graph_tracks_disjoint_buffer_range_writes_independently() {
+        let mut frame_graph = FrameGraph::default();
+
+        let instances = frame_graph.create("instances", buffer_desc());
+        let culling = frame_graph.create("culling", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        for view in 0..2 {
+            let mut builder = frame_graph.create_pass_node_builder(&format!("cull_{view}"));
+            builder.read(instances.clone());
+            builder.write_buffer_range(culling.clone(), view * 128, 128);
+        }
+
+        let culling = frame_graph
+            .get_resource_node(&culling.raw.index)
+            .get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("draw");
+            let slice = builder.read_buffer_range(culling.clone(), 0, 64);
+            assert_eq!(slice.range().end(), 64);
+            builder.write(output);
+        }
+
+        let cull_1 = frame_graph.get_pass_node(&IndexHandle::new(1));
+        assert_eq!(cull_1.writes[0].version, 2);
+        assert!(
+            cull_1
+                .reads
+                .iter()
+                .all(|read| read.index != culling.raw.index)
+        );
+
+        let draw = frame_graph.get_pass_node(&IndexHandle::new(2));
+        assert_eq!(draw.reads.len(), 1);
+        assert_eq!(draw.reads[0].version, 1);
+
+        frame_graph.compile().unwrap();
+
+        let culled = (0..3)
+            .map(|index| frame_graph.get_pass_node(&IndexHandle::new(index)).culled)
+            .collect::<Vec<_>>();
+        assert_eq!(culled, vec![false, true, false]);
+    }
+
+    #[test]
+    fn frame_graph_orders_partial_write_after_whole_resource_read() {
+        let mut frame_graph = FrameGraph::default();
+
+        let culling = frame_graph.create("culling", buffer_desc());
+        let scratch = frame_graph.create("scratch", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        let final_output = frame_graph.create("final_output", buffer_desc());
+        frame_graph.mark_output(&output);
+        frame_graph.mark_output(&final_output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("cull_0");
+            builder.write_buffer_range(culling.clone(), 0, 128);
+        }
+
+        let partial = frame_graph
+            .get_resource_node(&culling.raw.index)
+            .get_handle::<TransientBuffer>();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("readback");
+            builder.depends_on("prepare");
+            builder.read(partial.clone());
+            builder.write(output);
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("cull_1");
+            builder.write_buffer_range(partial, 128, 128);
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("prepare");
+            builder.write(scratch);
+        }
+
+        let culling = frame_graph
+            .get_resource_node(&culling.raw.index)
+            .get_handle::<TransientBuffer>();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("present");
+            builder.read(culling);
+            builder.write(final_output);
+        }
+
+        let readback = frame_graph.get_pass_node(&IndexHandle::new(1));
+        assert_eq!(readback.reads.len(), 1);
+        assert_eq!(readback.reads[0].version, 1);
+
+        frame_graph.compile().unwrap();
+
+        assert_eq!(
+            frame_graph.execution_order(),
+            &[
+                IndexHandle::new(0),
+                IndexHandle::new(3),
+                IndexHandle::new(1),
+                IndexHandle::new(2),
+                IndexHandle::new(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn frame_graph_orders_and_keeps_explicit_dependencies() {
+        let mut frame_graph = FrameGraph::default();
+
+        let staging = frame_graph.create("staging", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("consumer");
+            builder.depends_on("upload");
+            builder.write(output);
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("upload");
+            builder.write(staging.clone());
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("unrelated");
+            builder.write(staging);
+        }
+
+        frame_graph.compile().unwrap();
+
+        assert!(!frame_graph.get_pass_node(&IndexHandle::new(1)).culled);
+        assert!(frame_graph.get_pass_node(&IndexHandle::new(2)).culled);
+        assert_eq!(
+            frame_graph.execution_order(),
+            &[IndexHandle::new(1), IndexHandle::new(0)]
+        );
+    }
+
+    #[test]
+    fn frame_graph_rejects_cyclic_dependencies() {
+        let mut frame_graph = FrameGraph::default();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("first");
+            builder.depends_on("second");
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("second");
+            builder.depends_on("first");
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::CyclicDependencies {
+                passes: vec!["first".to_string(), "second".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn frame_graph_rejects_unknown_dependencies() {
+        let mut frame_graph = FrameGraph::default();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("present");
+            builder.depends_on("compose");
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::UnknownDependency {
+                pass: "present".to_string(),
+                dependency: "compose".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn frame_graph_execute_reuses_cached_resources_across_frames() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let recorder = RecordingResourceCreator::new(device.clone());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        for _ in 0..2 {
+            let mut frame_graph = FrameGraph::default();
+
+            let scratch = frame_graph.create("scratch", buffer_desc());
+            let output = frame_graph.create("output", buffer_desc());
+            frame_graph.mark_output(&output);
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("produce");
+                builder.write(scratch.clone());
+            }
+
+            let scratch = frame_graph
+                .get_resource_node(&scratch.raw.index)
+                .get_handle();
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("consume");
+                builder.read::<TransientBuffer>(scratch);
+                builder.write(output);
+            }
+
+            frame_graph.compile().unwrap();
+            assert_eq!(
+                frame_graph.execution_order(),
+                &[IndexHandle::new(0), IndexHandle::new(1)]
+            );
+
+            let mut context = FrameGraphContext::new(
+                PipelineContainer::default(),
+                &recorder,
+                &mut transient_resource_cache,
+            );
+
+            frame_graph.execute(&mut context);
+            context.finish(&queue);
+        }
+
+        assert_eq!(
+            recorder.created_resources(),
+            vec![buffer_desc().into(), buffer_desc().into()]
+        );
+        assert_eq!(transient_resource_cache.frame_index(), 2);
+    }
+
+    #[test]
+    fn frame_graph_exported_resources_are_not_returned_to_the_cache() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let recorder = RecordingResourceCreator::new(device.clone());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        for _ in 0..2 {
+            let mut frame_graph = FrameGraph::default();
+
+            let baked = frame_graph.create("baked", buffer_desc());
+            frame_graph.export(&baked);
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("bake");
+                builder.write(baked.clone());
+            }
+
+            frame_graph.compile().unwrap();
+
+            let mut context = FrameGraphContext::new(
+                PipelineContainer::default(),
+                &recorder,
+                &mut transient_resource_cache,
+            );
+            frame_graph.execute(&mut context);
+
+            let exported = context.take_exported(&baked).unwrap();
+            assert_eq!(exported.desc, buffer_desc());
+            assert!(context.take_exported(&baked).is_none());
+
+            context.finish(&queue);
+        }
+
+        assert_eq!(recorder.created_resources().len(), 2);
+    }
+
+    struct BindStorage {
+        bind_group: TransientBindGroup,
+    }
+
+    impl PassCommand for BindStorage {
+        fn execute(&self, context: &mut PassContext) {
+            context.get_or_create_bind_group(&self.bind_group);
+        }
+    }
+
+    #[test]
+    fn frame_graph_creates_bind_groups_through_the_resource_creator() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let recorder = RecordingResourceCreator::new(device.clone());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::COMPUTE,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let mut frame_graph = FrameGraph::default();
+
+        let culling = frame_graph.create("culling", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("cull");
+            let buffer = builder.read(culling);
+            builder.write(output);
+
+            builder.push(BindStorage {
+                bind_group: TransientBindGroup {
+                    label: Some("culling".to_string()),
+                    layout,
+                    entries: vec![TransientBindGroupEntry {
+                        binding: 0,
+                        resource: TransientBindGroupResource::Buffer(TransientBindGroupBuffer {
+                            buffer,
+                            size: None,
+                            offset: 0,
+                        }),
+                    }],
+                },
+            });
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &recorder,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        queue.submit(context.finish(&queue));
+
+        assert_eq!(
+            recorder.records(),
+            vec![
+                ResourceCreatorRecord::Resource(buffer_desc().into()),
+                ResourceCreatorRecord::Resource(buffer_desc().into()),
+                ResourceCreatorRecord::BindGroup(Some("culling".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn frame_graph_rejects_exported_resources_without_passes() {
+        let mut frame_graph = FrameGraph::default();
+
+        let baked = frame_graph.create("baked", buffer_desc());
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.export(&baked);
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("draw");
+            builder.write(output);
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::UnusedExport {
+                resource: "baked".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn frame_graph_import_derives_descriptors_from_wgpu_objects() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut frame_graph = FrameGraph::default();
+
+        let texture = frame_graph.import_texture(
+            "lut",
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 32,
+                    height: 16,
+                    depth_or_array_layers: 4,
+                },
+                mip_level_count: 3,
+                sample_count: 1,
+                dimension: TextureDimension::D3,
+                format: TextureFormat::R32Float,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            }),
+        );
+        let buffer = frame_graph.import_buffer(
+            "constants",
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            }),
+        );
+
+        let texture_desc = frame_graph
+            .get_resource_node(&texture.raw.index)
+            .resource
+            .get_desc::<TransientTexture>();
+        assert_eq!(texture_desc.label.as_deref(), Some("lut"));
+        assert_eq!(texture_desc.size.depth_or_array_layers, 4);
+        assert_eq!(texture_desc.mip_level_count, 3);
+        assert_eq!(texture_desc.dimension, TextureDimension::D3);
+        assert_eq!(texture_desc.format, TextureFormat::R32Float);
+
+        let buffer_desc = frame_graph
+            .get_resource_node(&buffer.raw.index)
+            .resource
+            .get_desc::<TransientBuffer>();
+        assert_eq!(buffer_desc.size, 64);
+        assert_eq!(buffer_desc.usage, BufferUsages::UNIFORM);
+    }
+
+    #[test]
+    fn frame_graph_renders_into_imported_texture_views() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let recorder = RecordingResourceCreator::new(device.clone());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let size = Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        let mut frame_graph = FrameGraph::default();
+
+        let surface = frame_graph.import_texture_view(
+            "surface",
+            texture.create_view(&Default::default()),
+            TextureFormat::Rgba8Unorm,
+            size,
+        );
+        assert_eq!(
+            frame_graph
+                .get_resource_node(&surface.raw.index)
+                .resource
+                .get_desc::<ImportedTextureView>(),
+            ImportedTextureViewDescriptor {
+                label: Some("surface".to_string()),
+                format: TextureFormat::Rgba8Unorm,
+                size,
+            }
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("present");
+            let surface = builder.write(surface);
+            builder
+                .create_render_pass_builder("present")
+                .add_color_attachment(TransientRenderPassColorAttachment {
+                    view: TextureViewEdge::ImportedWrite(surface),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::BLACK),
+                        store: StoreOp::Store,
+                    },
+                });
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &recorder,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        queue.submit(context.finish(&queue));
+
+        assert!(recorder.records().is_empty());
+        assert_eq!(transient_resource_cache.texture_view_cache().len(), 0);
+    }
+
+    #[test]
+    fn frame_graph_execute_collects_frame_stats() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        for frame in 0..2 {
+            let mut frame_graph = FrameGraph::default();
+
+            let scratch = frame_graph.create("scratch", buffer_desc());
+            let unused = frame_graph.create("unused", buffer_desc());
+            let output = frame_graph.create("output", buffer_desc());
+            frame_graph.mark_output(&output);
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("produce");
+                builder.write(scratch.clone());
+            }
+
+            let scratch = frame_graph
+                .get_resource_node(&scratch.raw.index)
+                .get_handle();
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("consume");
+                builder.read::<TransientBuffer>(scratch);
+                builder.write(output);
+            }
+
+            {
+                let mut builder = frame_graph.create_pass_node_builder("dead");
+                builder.write(unused);
+            }
+
+            frame_graph.compile().unwrap();
+
+            let mut context = FrameGraphContext::new(
+                PipelineContainer::default(),
+                &device,
+                &mut transient_resource_cache,
+            );
+            frame_graph.execute(&mut context);
+
+            let stats = context.stats();
+            assert_eq!(stats.passes_declared, 3);
+            assert_eq!(stats.passes_culled, 1);
+            assert_eq!(stats.passes_executed, 2);
+            assert_eq!(stats.get("consume").unwrap().draws, 0);
+            assert_eq!(stats.peak_live_bytes, 512);
+            assert_eq!(stats.live_bytes(), 0);
+
+            if frame == 0 {
+                assert_eq!((stats.resources_created, stats.resources_cached), (2, 0));
+                assert_eq!(stats.bytes_allocated, 512);
+            } else {
+                assert_eq!((stats.resources_created, stats.resources_cached), (0, 2));
+                assert_eq!(stats.bytes_allocated, 0);
+            }
+
+            context.finish(&queue);
+        }
+    }
+
+    #[test]
+    fn frame_graph_execute_reports_pass_timings_when_profiling() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_profiling(true);
+
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("clear");
+            builder.write(output);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        context.finish(&queue);
+
+        let report = frame_graph.profile_report().unwrap();
+        assert_eq!(report.passes.len(), 1);
+        assert_eq!(report.get("clear").unwrap().cpu_total(), report.cpu_total());
+        assert!(report.get("clear").unwrap().gpu.is_none());
+    }
+
+    #[test]
+    fn frame_graph_execute_captures_trace_events() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.set_trace_capture(true);
+
+        let output = frame_graph.create("output", buffer_desc());
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("clear");
+            builder.write(output);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        context.finish(&queue);
+
+        let trace = frame_graph.trace().unwrap();
+        let names = trace
+            .events()
+            .iter()
+            .map(|event| event.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "compile",
+                "clear",
+                "request",
+                "allocation",
+                "record",
+                "release"
+            ]
+        );
+
+        let events = trace.events();
+        let TraceEventKind::Span(request) = events[2].kind else {
+            panic!("request must be a span");
+        };
+        assert!(events[3].start >= events[2].start);
+        assert!(events[3].start <= events[2].start + request);
+        assert!(
+            trace
+                .to_chrome_trace_json()
+                .contains("\"resource\":\"output\"")
+        );
+    }
+
+    #[test]
+    fn pass_node_builder_rejects_overlapping_subresource_read_and_write() {
+        let mut frame_graph = FrameGraph::default();
+
+        let texture = frame_graph.create(
+            "texture",
+            TransientTextureDescriptor {
+                label: None,
+                size: Extent3d::default(),
+                mip_level_count: 2,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                view_formats: vec![],
+            },
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("feedback");
+            builder.read_subresource(texture.clone(), &TransientTextureViewDescriptor::default());
+            builder.write_subresource(
+                texture,
+                &TransientTextureViewDescriptor {
+                    base_mip_level: 1,
+                    ..Default::default()
+                },
+            );
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::RangeHazard {
+                pass: "feedback".to_string(),
+                resource: "texture".to_string(),
+            })
+        );
+    }
+}
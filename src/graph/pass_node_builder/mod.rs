@@ -2,13 +2,16 @@ mod parameter;
 mod pass_builder;
 mod render_pass_builder;
 
-pub use parameter::RenderPassExt;
+pub use parameter::*;
 pub use pass_builder::*;
 pub use render_pass_builder::*;
 
 use crate::{
-    FrameGraph, Pass, RawResourceHandle, ResourceHandle, ResourceMaterial, ResourceRead,
-    ResourceRef, ResourceWrite, TransientResource,
+    BufferRange, CompileError, FrameGraph, IndexHandle, Pass, RawResourceHandle, ResourceHandle,
+    ResourceMaterial, ResourceNode, ResourceRange, ResourceRead, ResourceReadWrite, ResourceRef,
+    ResourceWrite, TextureSubresourceRange, TransientBuffer, TransientBufferSlice,
+    TransientResource, TransientTexture, TransientTextureView, TransientTextureViewDescriptor,
+    UniformAllocation,
 };
 
 pub struct PassNodeBuilder<'a> {
@@ -16,6 +19,9 @@ pub struct PassNodeBuilder<'a> {
     pub(crate) name: String,
     writes: Vec<RawResourceHandle>,
     reads: Vec<RawResourceHandle>,
+    range_writes: Vec<(IndexHandle<ResourceNode>, Option<ResourceRange>)>,
+    range_reads: Vec<(IndexHandle<ResourceNode>, Option<ResourceRange>)>,
+    dependencies: Vec<String>,
     pass: Option<Pass>,
 }
 
@@ -24,6 +30,8 @@ impl Drop for PassNodeBuilder<'_> {
         let pass_node = self.graph.pass_node(&self.name);
         pass_node.writes = self.writes.clone();
         pass_node.reads = self.reads.clone();
+        pass_node.read_ranges = self.range_reads.clone();
+        pass_node.dependencies = self.dependencies.clone();
         pass_node.pass = self.pass.take();
     }
 }
@@ -39,47 +47,174 @@ pub trait PassNodeBuilderExt {
         material: &M,
     ) -> ResourceRef<M::ResourceType, ResourceWrite>;
 
-    fn read<ResourceType: TransientResource>(
+    fn read_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceRead>;
 
-    fn write<ResourceType: TransientResource>(
+    fn write_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceWrite>;
+
+    fn allocate_uniform(&mut self, data: &[u8]) -> UniformAllocation;
+
+    fn depends_on(&mut self, pass_name: &str);
+
+    fn read<ResourceType: TransientResource>(
+        &mut self,
+        resource_handle: ResourceHandle<ResourceType>,
+    ) -> ResourceRef<ResourceType, ResourceRead> {
+        self.read_range(resource_handle, None)
+    }
+
+    fn write<ResourceType: TransientResource>(
+        &mut self,
+        resource_handle: ResourceHandle<ResourceType>,
+    ) -> ResourceRef<ResourceType, ResourceWrite> {
+        self.write_range(resource_handle, None)
+    }
+
+    fn read_buffer_range(
+        &mut self,
+        resource_handle: ResourceHandle<TransientBuffer>,
+        offset: u64,
+        size: u64,
+    ) -> TransientBufferSlice<ResourceRead> {
+        let range = BufferRange { offset, size };
+
+        TransientBufferSlice {
+            buffer: self.read_range(resource_handle, Some(ResourceRange::Buffer(range))),
+            offset,
+            size,
+        }
+    }
+
+    fn write_buffer_range(
+        &mut self,
+        resource_handle: ResourceHandle<TransientBuffer>,
+        offset: u64,
+        size: u64,
+    ) -> TransientBufferSlice<ResourceWrite> {
+        let range = BufferRange { offset, size };
+
+        TransientBufferSlice {
+            buffer: self.write_range(resource_handle, Some(ResourceRange::Buffer(range))),
+            offset,
+            size,
+        }
+    }
+
+    fn read_subresource(
+        &mut self,
+        resource_handle: ResourceHandle<TransientTexture>,
+        desc: &TransientTextureViewDescriptor,
+    ) -> TransientTextureView<ResourceRead> {
+        let range = TextureSubresourceRange::from_view_desc(&resource_handle.desc, desc);
+
+        TransientTextureView {
+            texture: self.read_range(resource_handle, Some(ResourceRange::Texture(range))),
+            desc: desc.clone(),
+        }
+    }
+
+    fn write_subresource(
+        &mut self,
+        resource_handle: ResourceHandle<TransientTexture>,
+        desc: &TransientTextureViewDescriptor,
+    ) -> TransientTextureView<ResourceWrite> {
+        let range = TextureSubresourceRange::from_view_desc(&resource_handle.desc, desc);
+
+        TransientTextureView {
+            texture: self.write_range(resource_handle, Some(ResourceRange::Texture(range))),
+            desc: desc.clone(),
+        }
+    }
+
+    fn read_array<ResourceType: TransientResource>(
+        &mut self,
+        resource_handles: impl IntoIterator<Item = ResourceHandle<ResourceType>>,
+    ) -> Vec<ResourceRef<ResourceType, ResourceRead>> {
+        resource_handles
+            .into_iter()
+            .map(|resource_handle| self.read(resource_handle))
+            .collect()
+    }
+
+    fn read_write<ResourceType: TransientResource>(
+        &mut self,
+        resource_handle: ResourceHandle<ResourceType>,
+    ) -> ResourceRef<ResourceType, ResourceReadWrite> {
+        self.read_write_range(resource_handle, None)
+    }
+
+    fn read_write_range<ResourceType: TransientResource>(
+        &mut self,
+        resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
+    ) -> ResourceRef<ResourceType, ResourceReadWrite> {
+        self.read_range(resource_handle.clone(), range.clone());
+        let resource_ref = self.write_range(resource_handle, range);
+
+        ResourceRef::new(resource_ref.raw, resource_ref.desc)
+    }
 }
 
 impl<'a> PassNodeBuilderExt for PassNodeBuilder<'a> {
-    fn write<ResourceType: TransientResource>(
+    fn write_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceWrite> {
         let index = resource_handle.raw.index;
         let desc = resource_handle.desc.clone();
+        let range = self.bounded_range(index, range);
 
-        let resource_node = &mut self.graph.get_resource_node_mut(&index);
-        resource_node.new_version();
+        if let Some(error) = self.range_hazard(index, range.as_ref(), &self.range_reads) {
+            self.graph.builder_errors.push(error);
+        }
+        self.range_writes.push((index, range.clone()));
 
-        let new_raw = RawResourceHandle {
-            index,
-            version: resource_node.version(),
-        };
+        let resource_node = self.graph.get_resource_node_mut(&index);
+        let version = resource_node.write_range(range);
+
+        let new_raw = RawResourceHandle { index, version };
 
         self.writes.push(new_raw.clone());
 
         ResourceRef::new(new_raw, desc)
     }
 
-    fn read<ResourceType: TransientResource>(
+    fn read_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceRead> {
         let raw = resource_handle.raw;
         let desc = resource_handle.desc.clone();
+        let range = self.bounded_range(raw.index, range);
 
-        if !self.reads.contains(&raw) {
-            self.reads.push(raw.clone());
+        if let Some(error) = self.range_hazard(raw.index, range.as_ref(), &self.range_writes) {
+            self.graph.builder_errors.push(error);
+        }
+        self.range_reads.push((raw.index, range.clone()));
+
+        let versions = self
+            .graph
+            .get_resource_node(&raw.index)
+            .read_versions(range.as_ref(), raw.version);
+
+        for version in versions {
+            let read = RawResourceHandle {
+                index: raw.index,
+                version,
+            };
+
+            if !self.reads.contains(&read) {
+                self.reads.push(read);
+            }
         }
 
         ResourceRef::new(raw, desc)
@@ -99,6 +234,22 @@ impl<'a> PassNodeBuilderExt for PassNodeBuilder<'a> {
         let handle = material.imported(self.graph);
         self.write(handle)
     }
+
+    fn depends_on(&mut self, pass_name: &str) {
+        if !self.dependencies.iter().any(|name| name == pass_name) {
+            self.dependencies.push(pass_name.to_string());
+        }
+    }
+
+    fn allocate_uniform(&mut self, data: &[u8]) -> UniformAllocation {
+        let (handle, offset) = self.graph.allocate_uniform(data);
+
+        UniformAllocation {
+            buffer: self.read(handle),
+            offset,
+            size: data.len() as u64,
+        }
+    }
 }
 
 impl<'a> PassNodeBuilder<'a> {
@@ -113,7 +264,238 @@ impl<'a> PassNodeBuilder<'a> {
             name: name.to_string(),
             writes: vec![],
             reads: vec![],
+            range_writes: vec![],
+            range_reads: vec![],
+            dependencies: vec![],
             pass: None,
         }
     }
+
+    fn bounded_range(
+        &mut self,
+        index: IndexHandle<ResourceNode>,
+        range: Option<ResourceRange>,
+    ) -> Option<ResourceRange> {
+        let resource_node = self.graph.get_resource_node(&index);
+
+        if let Some(ResourceRange::Buffer(range)) = &range
+            && let Some(ResourceRange::Buffer(full)) =
+                ResourceRange::full(&resource_node.resource.get_any_desc())
+            && !range.fits(full.size)
+        {
+            let error = CompileError::BufferRangeOutOfBounds {
+                pass: self.name.clone(),
+                resource: resource_node.name.clone(),
+                offset: range.offset,
+                size: range.size,
+                buffer_size: full.size,
+            };
+            self.graph.builder_errors.push(error);
+
+            return Some(ResourceRange::Buffer(range.clamp(full.size)));
+        }
+
+        range
+    }
+
+    fn range_hazard(
+        &self,
+        index: IndexHandle<ResourceNode>,
+        range: Option<&ResourceRange>,
+        accesses: &[(IndexHandle<ResourceNode>, Option<ResourceRange>)],
+    ) -> Option<CompileError> {
+        let resource_node = self.graph.get_resource_node(&index);
+        let full = ResourceRange::full(&resource_node.resource.get_any_desc());
+        let normalize = |range: Option<&ResourceRange>| range.cloned().or(full.clone());
+
+        let range = normalize(range);
+        let partially_overlaps = |other_range: &Option<ResourceRange>| {
+            let other_range = normalize(other_range.as_ref());
+            match (&range, &other_range) {
+                _ if range == other_range => false,
+                (Some(range), Some(other_range)) => range.overlaps(other_range),
+                _ => true,
+            }
+        };
+
+        accesses
+            .iter()
+            .any(|(other_index, other_range)| {
+                *other_index == index && partially_overlaps(other_range)
+            })
+            .then(|| CompileError::RangeHazard {
+                pass: self.name.clone(),
+                resource: resource_node.name.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{BufferUsages, Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+    use crate::{
+        CompileError, FrameGraph, IndexHandle, PassNodeBuilderExt, ResourceRange,
+        TextureSubresourceRange, TransientBufferDescriptor, TransientTextureDescriptor,
+    };
+
+    #[test]
+    fn pass_node_builder_read_write_registers_read_and_new_version() {
+        let mut frame_graph = FrameGraph::default();
+
+        let buffer = frame_graph.create(
+            "storage",
+            TransientBufferDescriptor {
+                label: None,
+                size: 256,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("compute");
+            let buffer_ref = builder.read_write(buffer.clone());
+            assert_eq!(buffer_ref.raw.version, 1);
+        }
+
+        let pass_node = frame_graph.get_pass_node(&IndexHandle::new(0));
+        assert_eq!(pass_node.reads, vec![buffer.raw.clone()]);
+        assert_eq!(pass_node.writes.len(), 1);
+        assert_eq!(pass_node.writes[0].index, buffer.raw.index);
+        assert_eq!(pass_node.writes[0].version, 1);
+    }
+
+    #[test]
+    fn pass_node_builder_read_array_registers_every_read() {
+        let mut frame_graph = FrameGraph::default();
+
+        let buffers = (0..3)
+            .map(|index| {
+                frame_graph.create(
+                    &format!("material_{index}"),
+                    TransientBufferDescriptor {
+                        label: None,
+                        size: 64,
+                        usage: BufferUsages::STORAGE,
+                        mapped_at_creation: false,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("bindless");
+            let buffer_refs = builder.read_array(buffers.iter().cloned());
+            assert_eq!(buffer_refs.len(), 3);
+        }
+
+        let pass_node = frame_graph.get_pass_node(&IndexHandle::new(0));
+        assert_eq!(
+            pass_node.reads,
+            buffers
+                .iter()
+                .map(|buffer| buffer.raw.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn pass_node_builder_treats_unranged_access_as_whole_resource() {
+        let mut frame_graph = FrameGraph::default();
+
+        let buffer = frame_graph.create(
+            "culling",
+            TransientBufferDescriptor {
+                label: None,
+                size: 256,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("cull");
+            builder.read(buffer.clone());
+            builder.write_buffer_range(buffer.clone(), 0, 256);
+        }
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("compact");
+            builder.read(buffer.clone());
+            builder.write_buffer_range(buffer, 128, 128);
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::RangeHazard {
+                pass: "compact".to_string(),
+                resource: "culling".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn pass_node_builder_allows_read_modify_write_of_the_same_range() {
+        let mut frame_graph = FrameGraph::default();
+
+        let texture = frame_graph.create(
+            "storage_image",
+            TransientTextureDescriptor {
+                label: None,
+                size: Extent3d::default(),
+                mip_level_count: 4,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::STORAGE_BINDING,
+                view_formats: vec![],
+            },
+        );
+        frame_graph.mark_output(&texture);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("blur");
+            let range = TextureSubresourceRange {
+                mip_levels: 2..3,
+                array_layers: 0..1,
+            };
+            let texture_ref =
+                builder.read_write_range(texture, Some(ResourceRange::Texture(range)));
+            assert_eq!(texture_ref.raw.version, 1);
+        }
+
+        assert_eq!(frame_graph.compile(), Ok(()));
+    }
+
+    #[test]
+    fn pass_node_builder_rejects_buffer_range_beyond_buffer_size() {
+        let mut frame_graph = FrameGraph::default();
+
+        let buffer = frame_graph.create(
+            "culling",
+            TransientBufferDescriptor {
+                label: None,
+                size: 256,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("cull");
+            builder.read_buffer_range(buffer, 192, u64::MAX);
+        }
+
+        assert_eq!(
+            frame_graph.compile(),
+            Err(CompileError::BufferRangeOutOfBounds {
+                pass: "cull".to_string(),
+                resource: "culling".to_string(),
+                offset: 192,
+                size: u64::MAX,
+                buffer_size: 256,
+            })
+        );
+    }
 }
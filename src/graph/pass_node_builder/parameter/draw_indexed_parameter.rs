@@ -1,6 +1,8 @@
-use crate::{RenderPassCommand, RenderPassContext};
+use crate::{CaptureObjects, CapturedRenderPassCommand, RenderPassCommand, RenderPassContext};
 use std::ops::Range;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawIndexedParameter {
     pub indices: Range<u32>,
     pub base_vertex: i32,
@@ -15,4 +17,8 @@ impl RenderPassCommand for DrawIndexedParameter {
             self.instances.clone(),
         );
     }
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::DrawIndexed(self.clone())
+    }
 }
@@ -1,6 +1,8 @@
-use crate::{RenderPassCommand, RenderPassContext};
+use crate::{CaptureObjects, CapturedRenderPassCommand, RenderPassCommand, RenderPassContext};
 use std::ops::Range;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawParameter {
     pub vertices: Range<u32>,
     pub instances: Range<u32>,
@@ -10,4 +12,8 @@ impl RenderPassCommand for DrawParameter {
     fn execute(&self, render_pass_context: &mut RenderPassContext) {
         render_pass_context.draw(self.vertices.clone(), self.instances.clone());
     }
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::Draw(self.clone())
+    }
 }
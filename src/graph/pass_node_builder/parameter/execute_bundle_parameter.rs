@@ -0,0 +1,26 @@
+use crate::{
+    CaptureObjects, CapturedRenderPassCommand, RenderPassCommand, RenderPassContext,
+    TransientRenderBundle,
+};
+
+pub struct ExecuteBundleParameter {
+    pub(crate) bundle: TransientRenderBundle,
+}
+
+impl RenderPassCommand for ExecuteBundleParameter {
+    fn execute(&self, render_pass_context: &mut RenderPassContext) {
+        render_pass_context.execute_bundle(&self.bundle);
+    }
+
+    fn capture(&self, objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::ExecuteBundle {
+            desc: self.bundle.desc.clone(),
+            commands: self
+                .bundle
+                .commands
+                .iter()
+                .map(|command| command.capture(objects))
+                .collect(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 mod draw_indexed_parameter;
 mod draw_parameter;
+mod execute_bundle_parameter;
 mod set_bind_group_parameter;
 mod set_index_buffer_parameter;
 mod set_render_pipeline_parameter;
@@ -7,13 +8,15 @@ mod set_vertex_buffer_parameter;
 
 use crate::{
     RenderPass, RenderPassCommand, ResourceRead, ResourceRef, TransientBindGroup, TransientBuffer,
+    TransientBufferSlice, TransientRenderBundle,
 };
-use draw_indexed_parameter::*;
-use draw_parameter::*;
-use set_bind_group_parameter::*;
-use set_index_buffer_parameter::*;
-use set_render_pipeline_parameter::*;
-use set_vertex_buffer_parameter::*;
+pub use draw_indexed_parameter::*;
+pub use draw_parameter::*;
+pub use execute_bundle_parameter::*;
+pub use set_bind_group_parameter::*;
+pub use set_index_buffer_parameter::*;
+pub use set_render_pipeline_parameter::*;
+pub use set_vertex_buffer_parameter::*;
 use std::ops::Range;
 use wgpu::{IndexFormat, RenderPipeline};
 
@@ -76,6 +79,18 @@ pub trait RenderPassExt {
             size,
         });
     }
+
+    fn set_vertex_buffer_slice(&mut self, slot: u32, slice: &TransientBufferSlice<ResourceRead>) {
+        self.set_vertex_buffer(slot, &slice.buffer, slice.offset, slice.size);
+    }
+
+    fn set_index_buffer_slice(
+        &mut self,
+        slice: &TransientBufferSlice<ResourceRead>,
+        index_format: IndexFormat,
+    ) {
+        self.set_index_buffer(&slice.buffer, index_format, slice.offset, slice.size);
+    }
 }
 
 impl RenderPassExt for RenderPass {
@@ -83,3 +98,15 @@ impl RenderPassExt for RenderPass {
         self.commands.push(Box::new(value));
     }
 }
+
+impl RenderPass {
+    pub fn execute_bundle(&mut self, bundle: TransientRenderBundle) {
+        self.push(ExecuteBundleParameter { bundle });
+    }
+}
+
+impl RenderPassExt for TransientRenderBundle {
+    fn push<T: RenderPassCommand>(&mut self, value: T) {
+        self.commands.push(Box::new(value));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{RenderPassCommand, RenderPassContext, TransientBindGroup};
+use crate::{
+    CaptureObjects, CapturedBindGroup, CapturedRenderPassCommand, RenderPassCommand,
+    RenderPassContext, TransientBindGroup,
+};
 
 pub struct SetBindGroupParameter {
     pub index: u32,
@@ -10,4 +13,12 @@ impl RenderPassCommand for SetBindGroupParameter {
     fn execute(&self, render_pass_context: &mut RenderPassContext) {
         render_pass_context.set_bind_group(self.index, &self.bind_group, &self.offsets);
     }
+
+    fn capture(&self, objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::SetBindGroup {
+            index: self.index,
+            bind_group: CapturedBindGroup::new(&self.bind_group, objects),
+            offsets: self.offsets.clone(),
+        }
+    }
 }
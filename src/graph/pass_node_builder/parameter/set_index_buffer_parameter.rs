@@ -1,6 +1,9 @@
 use wgpu::IndexFormat;
 
-use crate::{RenderPassCommand, RenderPassContext, ResourceRead, ResourceRef, TransientBuffer};
+use crate::{
+    CaptureObjects, CapturedRenderPassCommand, CapturedResourceRef, RenderPassCommand,
+    RenderPassContext, ResourceRead, ResourceRef, TransientBuffer,
+};
 
 pub struct SetIndexBufferParameter {
     pub buffer_ref: ResourceRef<TransientBuffer, ResourceRead>,
@@ -18,4 +21,13 @@ impl RenderPassCommand for SetIndexBufferParameter {
             self.size,
         );
     }
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::SetIndexBuffer {
+            buffer: CapturedResourceRef::new(&self.buffer_ref),
+            index_format: self.index_format,
+            offset: self.offset,
+            size: self.size,
+        }
+    }
 }
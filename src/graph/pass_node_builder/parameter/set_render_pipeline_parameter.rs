@@ -1,6 +1,6 @@
 use wgpu::RenderPipeline;
 
-use crate::{RenderPassCommand, RenderPassContext};
+use crate::{CaptureObjects, CapturedRenderPassCommand, RenderPassCommand, RenderPassContext};
 
 pub struct SetRenderPipelineParameter {
     pub pipeline: RenderPipeline,
@@ -10,4 +10,8 @@ impl RenderPassCommand for SetRenderPipelineParameter {
     fn execute(&self, render_pass_context: &mut RenderPassContext) {
         render_pass_context.set_render_pipeline(&self.pipeline);
     }
+
+    fn capture(&self, objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::SetRenderPipeline(objects.render_pipeline(&self.pipeline))
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{RenderPassCommand, RenderPassContext, ResourceRead, ResourceRef, TransientBuffer};
+use crate::{
+    CaptureObjects, CapturedRenderPassCommand, CapturedResourceRef, RenderPassCommand,
+    RenderPassContext, ResourceRead, ResourceRef, TransientBuffer,
+};
 
 pub struct SetVertexBufferParameter {
     pub slot: u32,
@@ -11,4 +14,13 @@ impl RenderPassCommand for SetVertexBufferParameter {
     fn execute(&self, render_pass_context: &mut RenderPassContext) {
         render_pass_context.set_vertex_buffer(self.slot, &self.buffer_ref, self.offset, self.size);
     }
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::SetVertexBuffer {
+            slot: self.slot,
+            buffer: CapturedResourceRef::new(&self.buffer_ref),
+            offset: self.offset,
+            size: self.size,
+        }
+    }
 }
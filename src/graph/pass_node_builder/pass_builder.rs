@@ -2,7 +2,7 @@ use std::mem::take;
 
 use crate::{
     Pass, PassCommand, PassNodeBuilderExt, RenderPassBuilder, ResourceHandle, ResourceMaterial,
-    ResourceRead, ResourceRef, ResourceWrite, TransientResource,
+    ResourceRange, ResourceRead, ResourceRef, ResourceWrite, TransientResource, UniformAllocation,
 };
 
 use super::PassNodeBuilder;
@@ -34,18 +34,28 @@ impl<'a> PassNodeBuilderExt for PassBuilder<'a> {
         self.pass_node_builder.write_material(material)
     }
 
-    fn read<ResourceType: TransientResource>(
+    fn read_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceRead> {
-        self.pass_node_builder.read(resource_handle)
+        self.pass_node_builder.read_range(resource_handle, range)
     }
 
-    fn write<ResourceType: TransientResource>(
+    fn allocate_uniform(&mut self, data: &[u8]) -> UniformAllocation {
+        self.pass_node_builder.allocate_uniform(data)
+    }
+
+    fn depends_on(&mut self, pass_name: &str) {
+        self.pass_node_builder.depends_on(pass_name)
+    }
+
+    fn write_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceWrite> {
-        self.pass_node_builder.write(resource_handle)
+        self.pass_node_builder.write_range(resource_handle, range)
     }
 }
 
@@ -3,8 +3,9 @@ use std::{mem::take, ops::Range};
 use wgpu::{IndexFormat, RenderPipeline};
 
 use crate::{
-    PassNodeBuilderExt, RenderPass, ResourceHandle, ResourceMaterial, ResourceRead, ResourceRef,
-    ResourceWrite, TransientBuffer, TransientRenderPassColorAttachment, TransientResource,
+    PassNodeBuilderExt, RenderPass, ResourceHandle, ResourceMaterial, ResourceRange, ResourceRead,
+    ResourceRef, ResourceWrite, TransientBuffer, TransientBufferSlice, TransientRenderBundle,
+    TransientRenderPassColorAttachment, TransientResource, UniformAllocation,
 };
 
 use super::{PassBuilder, RenderPassExt};
@@ -35,18 +36,28 @@ impl PassNodeBuilderExt for RenderPassBuilder<'_, '_> {
         self.pass_builder.write_material(material)
     }
 
-    fn read<ResourceType: TransientResource>(
+    fn read_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceRead> {
-        self.pass_builder.read(resource_handle)
+        self.pass_builder.read_range(resource_handle, range)
     }
 
-    fn write<ResourceType: TransientResource>(
+    fn allocate_uniform(&mut self, data: &[u8]) -> UniformAllocation {
+        self.pass_builder.allocate_uniform(data)
+    }
+
+    fn depends_on(&mut self, pass_name: &str) {
+        self.pass_builder.depends_on(pass_name)
+    }
+
+    fn write_range<ResourceType: TransientResource>(
         &mut self,
         resource_handle: ResourceHandle<ResourceType>,
+        range: Option<ResourceRange>,
     ) -> ResourceRef<ResourceType, ResourceWrite> {
-        self.pass_builder.write(resource_handle)
+        self.pass_builder.write_range(resource_handle, range)
     }
 }
 
@@ -118,6 +129,29 @@ impl<'a, 'b> RenderPassBuilder<'a, 'b> {
         self
     }
 
+    pub fn set_vertex_buffer_slice(
+        &mut self,
+        slot: u32,
+        slice: &TransientBufferSlice<ResourceRead>,
+    ) -> &mut Self {
+        self.render_pass.set_vertex_buffer_slice(slot, slice);
+        self
+    }
+
+    pub fn set_index_buffer_slice(
+        &mut self,
+        slice: &TransientBufferSlice<ResourceRead>,
+        index_format: IndexFormat,
+    ) -> &mut Self {
+        self.render_pass.set_index_buffer_slice(slice, index_format);
+        self
+    }
+
+    pub fn execute_bundle(&mut self, bundle: TransientRenderBundle) -> &mut Self {
+        self.render_pass.execute_bundle(bundle);
+        self
+    }
+
     pub fn create_render_pass_builder(&mut self) -> &mut Self {
         self.finish();
 
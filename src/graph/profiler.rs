@@ -0,0 +1,176 @@
+use std::{sync::mpsc, time::Duration};
+
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device, Features, MapMode,
+    PollType, QUERY_SIZE, QuerySet, QuerySetDescriptor, QueryType,
+};
+
+use crate::FrameGraphContext;
+
+#[derive(Clone, Debug, Default)]
+pub struct PassProfile {
+    pub name: String,
+    pub request: Duration,
+    pub record: Duration,
+    pub release: Duration,
+    pub gpu: Option<Duration>,
+}
+
+impl PassProfile {
+    pub fn cpu_total(&self) -> Duration {
+        self.request + self.record + self.release
+    }
+}
+
+#[derive(Default)]
+pub struct FrameProfileReport {
+    pub passes: Vec<PassProfile>,
+    gpu_readback: Option<Buffer>,
+}
+
+impl FrameProfileReport {
+    pub fn get(&self, name: &str) -> Option<&PassProfile> {
+        self.passes.iter().find(|pass| pass.name == name)
+    }
+
+    pub fn cpu_total(&self) -> Duration {
+        self.passes.iter().map(PassProfile::cpu_total).sum()
+    }
+
+    pub fn has_pending_gpu_timings(&self) -> bool {
+        self.gpu_readback.is_some()
+    }
+
+    pub fn resolve_gpu_timings(&mut self, device: &Device, timestamp_period: f32) -> bool {
+        let Some(readback) = self.gpu_readback.take() else {
+            return false;
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        readback.slice(..).map_async(MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        if device.poll(PollType::wait_indefinitely()).is_err()
+            || !matches!(receiver.try_recv(), Ok(Ok(())))
+        {
+            return false;
+        }
+
+        let timestamps = readback
+            .slice(..)
+            .get_mapped_range()
+            .chunks_exact(QUERY_SIZE as usize)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        readback.unmap();
+
+        for (index, pass) in self.passes.iter_mut().enumerate() {
+            let begin = timestamps[index * 2];
+            let end = timestamps[index * 2 + 1];
+            let nanos = end.saturating_sub(begin) as f64 * timestamp_period as f64;
+
+            pass.gpu = Some(Duration::from_nanos(nanos as u64));
+        }
+
+        true
+    }
+}
+
+struct GpuTimestampQueries {
+    query_set: QuerySet,
+    count: u32,
+}
+
+impl GpuTimestampQueries {
+    fn new(device: &Device, pass_count: usize) -> Option<Self> {
+        let features = Features::TIMESTAMP_QUERY | Features::TIMESTAMP_QUERY_INSIDE_ENCODERS;
+        if !device.features().contains(features) || pass_count == 0 {
+            return None;
+        }
+
+        let count = pass_count as u32 * 2;
+        let query_set = device.create_query_set(&QuerySetDescriptor {
+            label: Some("frame_graph_profiler"),
+            ty: QueryType::Timestamp,
+            count,
+        });
+
+        Some(Self { query_set, count })
+    }
+
+    fn write_timestamp(&self, context: &mut FrameGraphContext, query_index: u32) {
+        let mut command_encoder = context
+            .device()
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        command_encoder.write_timestamp(&self.query_set, query_index);
+
+        context.add_command_buffer(command_encoder.finish());
+    }
+
+    fn resolve(self, context: &mut FrameGraphContext) -> Buffer {
+        let size = (self.count * QUERY_SIZE) as u64;
+
+        let resolve_buffer = context.device().create_buffer(&BufferDescriptor {
+            label: Some("frame_graph_profiler_resolve"),
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = context.device().create_buffer(&BufferDescriptor {
+            label: Some("frame_graph_profiler_readback"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut command_encoder = context
+            .device()
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        command_encoder.resolve_query_set(&self.query_set, 0..self.count, &resolve_buffer, 0);
+        command_encoder.copy_buffer_to_buffer(&resolve_buffer, 0, &readback_buffer, 0, size);
+
+        context.add_command_buffer(command_encoder.finish());
+
+        readback_buffer
+    }
+}
+
+pub(crate) struct FrameProfiler {
+    gpu_timestamp_queries: Option<GpuTimestampQueries>,
+    passes: Vec<PassProfile>,
+}
+
+impl FrameProfiler {
+    pub fn new(device: &Device, pass_count: usize) -> Self {
+        Self {
+            gpu_timestamp_queries: GpuTimestampQueries::new(device, pass_count),
+            passes: Vec::with_capacity(pass_count),
+        }
+    }
+
+    pub fn begin_pass(&self, context: &mut FrameGraphContext) {
+        if let Some(queries) = &self.gpu_timestamp_queries {
+            queries.write_timestamp(context, self.passes.len() as u32 * 2);
+        }
+    }
+
+    pub fn end_pass(&self, context: &mut FrameGraphContext) {
+        if let Some(queries) = &self.gpu_timestamp_queries {
+            queries.write_timestamp(context, self.passes.len() as u32 * 2 + 1);
+        }
+    }
+
+    pub fn push(&mut self, pass: PassProfile) {
+        self.passes.push(pass);
+    }
+
+    pub fn finish(self, context: &mut FrameGraphContext) -> FrameProfileReport {
+        FrameProfileReport {
+            passes: self.passes,
+            gpu_readback: self
+                .gpu_timestamp_queries
+                .map(|queries| queries.resolve(context)),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    AnyTransientResourceDescriptor, FrameGraph, HistorySlot, IndexHandle, RawResourceHandle,
+    ResourceNode, VirtualResource,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceVersionSnapshot {
+    pub resource: String,
+    pub version: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassSnapshot {
+    pub name: String,
+    pub culled: bool,
+    pub dependencies: Vec<String>,
+    pub reads: Vec<ResourceVersionSnapshot>,
+    pub writes: Vec<ResourceVersionSnapshot>,
+    pub requests: Vec<String>,
+    pub releases: Vec<String>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceKindSnapshot {
+    Transient,
+    Imported,
+    History { key: String, slot: HistorySlot },
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceSnapshot {
+    pub name: String,
+    pub kind: ResourceKindSnapshot,
+    pub desc: AnyTransientResourceDescriptor,
+    pub output: bool,
+    pub exported: bool,
+    pub first_use_pass: Option<String>,
+    pub last_use_pass: Option<String>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameGraphSnapshot {
+    pub execution_order: Vec<String>,
+    pub passes: Vec<PassSnapshot>,
+    pub resources: Vec<ResourceSnapshot>,
+}
+
+impl FrameGraphSnapshot {
+    pub fn new(frame_graph: &FrameGraph) -> Self {
+        let resource_name = |index: &IndexHandle<ResourceNode>| {
+            frame_graph.resource_nodes[index.index].name.clone()
+        };
+        let resource_version = |handle: &RawResourceHandle| ResourceVersionSnapshot {
+            resource: resource_name(&handle.index),
+            version: handle.version,
+        };
+
+        let execution_order = frame_graph
+            .execution_order
+            .iter()
+            .map(|handle| frame_graph.get_pass_node(handle).name.clone())
+            .collect();
+
+        let passes = frame_graph
+            .pass_nodes
+            .iter()
+            .map(|pass_node| PassSnapshot {
+                name: pass_node.name.clone(),
+                culled: pass_node.culled,
+                dependencies: pass_node.dependencies.clone(),
+                reads: pass_node.reads.iter().map(resource_version).collect(),
+                writes: pass_node.writes.iter().map(resource_version).collect(),
+                requests: pass_node
+                    .resource_request_array
+                    .iter()
+                    .map(resource_name)
+                    .collect(),
+                releases: pass_node
+                    .resource_release_array
+                    .iter()
+                    .map(resource_name)
+                    .collect(),
+            })
+            .collect();
+
+        let pass_name = |handle: &Option<IndexHandle<_>>| {
+            handle.map(|handle| frame_graph.get_pass_node(&handle).name.clone())
+        };
+
+        let resources = frame_graph
+            .resource_nodes
+            .iter()
+            .map(|resource_node| {
+                let (kind, desc) = match &resource_node.resource {
+                    VirtualResource::Setuped(desc) => {
+                        (ResourceKindSnapshot::Transient, desc.clone())
+                    }
+                    VirtualResource::Imported(resource) => {
+                        (ResourceKindSnapshot::Imported, resource.get_desc())
+                    }
+                    VirtualResource::History(history) => (
+                        ResourceKindSnapshot::History {
+                            key: history.key.clone(),
+                            slot: history.slot,
+                        },
+                        history.desc.clone(),
+                    ),
+                };
+
+                ResourceSnapshot {
+                    name: resource_node.name.clone(),
+                    kind,
+                    desc,
+                    output: resource_node.output,
+                    exported: resource_node.exported,
+                    first_use_pass: pass_name(&resource_node.first_use_pass),
+                    last_use_pass: pass_name(&resource_node.last_user_pass),
+                }
+            })
+            .collect();
+
+        Self {
+            execution_order,
+            passes,
+            resources,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use wgpu::BufferUsages;
+
+    use crate::{FrameGraph, PassNodeBuilderExt, TransientBuffer, TransientBufferDescriptor};
+
+    #[test]
+    fn frame_graph_snapshot_serializes_compiled_structure() {
+        let mut frame_graph = FrameGraph::default();
+
+        let scratch = frame_graph.create(
+            "scratch",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        let output = frame_graph.create(
+            "output",
+            TransientBufferDescriptor {
+                label: Some("output".to_string()),
+                size: 128,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        frame_graph.mark_output(&output);
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("produce");
+            builder.write(scratch.clone());
+        }
+
+        let scratch = frame_graph
+            .get_resource_node(&scratch.raw.index)
+            .get_handle();
+
+        {
+            let mut builder = frame_graph.create_pass_node_builder("consume");
+            builder.read::<TransientBuffer>(scratch);
+            builder.write(output);
+        }
+
+        frame_graph.compile().unwrap();
+
+        let snapshot = frame_graph.snapshot();
+        let json = serde_json::to_string_pretty(&snapshot).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{
+  "execution_order": [
+    "produce",
+    "consume"
+  ],
+  "passes": [
+    {
+      "name": "produce",
+      "culled": false,
+      "dependencies": [],
+      "reads": [],
+      "writes": [
+        {
+          "resource": "scratch",
+          "version": 1
+        }
+      ],
+      "requests": [
+        "scratch"
+      ],
+      "releases": []
+    },
+    {
+      "name": "consume",
+      "culled": false,
+      "dependencies": [],
+      "reads": [
+        {
+          "resource": "scratch",
+          "version": 1
+        }
+      ],
+      "writes": [
+        {
+          "resource": "output",
+          "version": 1
+        }
+      ],
+      "requests": [
+        "output"
+      ],
+      "releases": [
+        "scratch",
+        "output"
+      ]
+    }
+  ],
+  "resources": [
+    {
+      "name": "scratch",
+      "kind": "Transient",
+      "desc": {
+        "Buffer": {
+          "label": null,
+          "size": 64,
+          "usage": "COPY_DST | STORAGE",
+          "mapped_at_creation": false
+        }
+      },
+      "output": false,
+      "exported": false,
+      "first_use_pass": "produce",
+      "last_use_pass": "consume"
+    },
+    {
+      "name": "output",
+      "kind": "Transient",
+      "desc": {
+        "Buffer": {
+          "label": "output",
+          "size": 128,
+          "usage": "STORAGE",
+          "mapped_at_creation": false
+        }
+      },
+      "output": true,
+      "exported": false,
+      "first_use_pass": "consume",
+      "last_use_pass": "consume"
+    }
+  ]
+}"#
+        );
+
+        let parsed: crate::FrameGraphSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, snapshot);
+    }
+}
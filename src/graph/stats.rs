@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::{
+    IndexHandle, ResourceAcquisition, ResourceNode, ResourceRelease, ResourceRequese,
+    VirtualResource,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PassStats {
+    pub name: String,
+    pub draws: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FrameGraphStats {
+    pub passes_declared: usize,
+    pub passes_culled: usize,
+    pub passes_executed: usize,
+    pub resources_created: usize,
+    pub resources_cached: usize,
+    pub bytes_allocated: u64,
+    pub peak_live_bytes: u64,
+    pub bind_groups_created: u64,
+    pub texture_views_created: u64,
+    pub passes: Vec<PassStats>,
+    live_resources: HashMap<IndexHandle<ResourceNode>, u64>,
+    live_bytes: u64,
+}
+
+impl FrameGraphStats {
+    pub fn new(passes_declared: usize, passes_culled: usize) -> Self {
+        Self {
+            passes_declared,
+            passes_culled,
+            ..Default::default()
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PassStats> {
+        self.passes.iter().find(|pass| pass.name == name)
+    }
+
+    pub fn draws(&self) -> u32 {
+        self.passes.iter().map(|pass| pass.draws).sum()
+    }
+
+    pub fn live_bytes(&self) -> u64 {
+        self.live_bytes
+    }
+
+    pub(crate) fn record_acquisition(
+        &mut self,
+        request: &ResourceRequese,
+        acquisition: ResourceAcquisition,
+    ) {
+        let VirtualResource::Setuped(desc) = &request.resource else {
+            return;
+        };
+
+        let bytes = desc.size_in_bytes();
+
+        match acquisition {
+            ResourceAcquisition::Created => {
+                self.resources_created += 1;
+                self.bytes_allocated += bytes;
+            }
+            ResourceAcquisition::Cached => self.resources_cached += 1,
+            _ => return,
+        }
+
+        self.live_resources.insert(request.index, bytes);
+        self.live_bytes += bytes;
+        self.peak_live_bytes = self.peak_live_bytes.max(self.live_bytes);
+    }
+
+    pub(crate) fn record_release(&mut self, release: &ResourceRelease) {
+        if let Some(bytes) = self.live_resources.remove(&release.index) {
+            self.live_bytes -= bytes;
+        }
+    }
+
+    pub(crate) fn push_pass(&mut self, pass: PassStats) {
+        self.passes_executed += 1;
+        self.passes.push(pass);
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::ResourceAcquisition;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceEventKind {
+    Span(Duration),
+    Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct TraceEvent {
+    pub name: String,
+    pub category: &'static str,
+    pub start: Duration,
+    pub kind: TraceEventKind,
+    pub args: Vec<(String, String)>,
+}
+
+pub struct FrameTrace {
+    origin: Instant,
+    events: Vec<TraceEvent>,
+}
+
+impl Default for FrameTrace {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            events: vec![],
+        }
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char if char.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", char as u32);
+            }
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+impl FrameTrace {
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    pub fn add_span(
+        &mut self,
+        name: &str,
+        category: &'static str,
+        start: Instant,
+        duration: Duration,
+    ) {
+        self.events.push(TraceEvent {
+            name: name.to_string(),
+            category,
+            start: start.saturating_duration_since(self.origin),
+            kind: TraceEventKind::Span(duration),
+            args: vec![],
+        });
+    }
+
+    pub fn add_instant(
+        &mut self,
+        name: &str,
+        category: &'static str,
+        at: Instant,
+        args: Vec<(String, String)>,
+    ) {
+        self.events.push(TraceEvent {
+            name: name.to_string(),
+            category,
+            start: at.saturating_duration_since(self.origin),
+            kind: TraceEventKind::Instant,
+            args,
+        });
+    }
+
+    pub fn add_acquisition(
+        &mut self,
+        resource: &str,
+        acquisition: ResourceAcquisition,
+        at: Instant,
+    ) {
+        let name = match acquisition {
+            ResourceAcquisition::Imported => "imported",
+            ResourceAcquisition::History => "history",
+            ResourceAcquisition::Cached => "cache hit",
+            ResourceAcquisition::Created => "allocation",
+        };
+
+        self.add_instant(
+            name,
+            "resource",
+            at,
+            vec![("resource".to_string(), resource.to_string())],
+        );
+    }
+
+    pub fn to_chrome_trace_json(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+
+        for (index, event) in self.events.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            let _ = write!(
+                json,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"pid\":0,\"tid\":0,\"ts\":{:.3}",
+                escape_json(&event.name),
+                event.category,
+                event.start.as_secs_f64() * 1_000_000.0
+            );
+
+            match event.kind {
+                TraceEventKind::Span(duration) => {
+                    let _ = write!(
+                        json,
+                        ",\"ph\":\"X\",\"dur\":{:.3}",
+                        duration.as_secs_f64() * 1_000_000.0
+                    );
+                }
+                TraceEventKind::Instant => json.push_str(",\"ph\":\"i\",\"s\":\"t\""),
+            }
+
+            if !event.args.is_empty() {
+                json.push_str(",\"args\":{");
+                for (arg_index, (key, value)) in event.args.iter().enumerate() {
+                    if arg_index > 0 {
+                        json.push(',');
+                    }
+                    let _ = write!(json, "\"{}\":\"{}\"", escape_json(key), escape_json(value));
+                }
+                json.push('}');
+            }
+
+            json.push('}');
+        }
+
+        json.push_str("],\"displayTimeUnit\":\"ms\"}");
+        json
+    }
+
+    pub fn write_chrome_trace(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_chrome_trace_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::FrameTrace;
+
+    #[test]
+    fn frame_trace_writes_chrome_trace_events() {
+        let mut trace = FrameTrace::default();
+        let now = Instant::now();
+
+        trace.add_span("shadow \"pass\"", "pass", now, Duration::from_micros(250));
+        trace.add_instant(
+            "allocation",
+            "resource",
+            now,
+            vec![("resource".to_string(), "depth".to_string())],
+        );
+
+        let json = trace.to_chrome_trace_json();
+
+        assert!(json.starts_with("{\"traceEvents\":[{\"name\":\"shadow \\\"pass\\\"\""));
+        assert!(json.contains("\"ph\":\"X\",\"dur\":250.000"));
+        assert!(json.contains("\"ph\":\"i\",\"s\":\"t\",\"args\":{\"resource\":\"depth\"}"));
+    }
+}
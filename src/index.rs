@@ -1,5 +1,6 @@
 use core::{
     any::{Any, TypeId},
+    fmt::{self, Debug},
     hash::{Hash, Hasher},
     marker::PhantomData,
 };
@@ -25,6 +26,12 @@ impl<T> PartialEq for IndexHandle<T> {
     }
 }
 
+impl<T> Debug for IndexHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IndexHandle").field(&self.index).finish()
+    }
+}
+
 impl<T> Copy for IndexHandle<T> {}
 
 impl<T> Clone for IndexHandle<T> {
@@ -1,32 +1,63 @@
 mod bind_group;
+mod buffer_slice;
+mod capture;
+mod debug_fill;
 mod graph;
 mod index;
 mod pass;
 mod pass_node;
 mod pipeline_container;
+mod presentation;
+mod recording_resource_creator;
+mod relative_size;
 mod resource_board;
 mod resource_node;
+mod resource_range;
 mod resource_table;
 mod texture_view;
 mod transient_resource;
+mod uniform_allocator;
 
 pub use bind_group::*;
+pub use buffer_slice::*;
+pub use capture::*;
+pub use debug_fill::*;
 pub use graph::*;
 pub use index::*;
 pub use pass::*;
 pub use pass_node::*;
 pub use pipeline_container::*;
+pub use presentation::*;
+pub use recording_resource_creator::*;
+pub use relative_size::*;
 pub use resource_board::*;
 pub use resource_node::*;
+pub use resource_range::*;
 pub use resource_table::*;
 pub use texture_view::*;
 pub use transient_resource::*;
+pub use uniform_allocator::*;
 
 use wgpu::{BindGroup, BindGroupEntry, Device};
 
 pub trait TransientResourceCreator {
     fn create_resource(&self, desc: &AnyTransientResourceDescriptor) -> AnyTransientResource;
     fn create_bind_group(&self, desc: &TransientBindGroupDescriptor) -> BindGroup;
+    fn device(&self) -> &Device;
+}
+
+impl<T: TransientResourceCreator + ?Sized> TransientResourceCreator for &T {
+    fn create_resource(&self, desc: &AnyTransientResourceDescriptor) -> AnyTransientResource {
+        (**self).create_resource(desc)
+    }
+
+    fn create_bind_group(&self, desc: &TransientBindGroupDescriptor) -> BindGroup {
+        (**self).create_bind_group(desc)
+    }
+
+    fn device(&self) -> &Device {
+        (**self).device()
+    }
 }
 
 impl TransientResourceCreator for Device {
@@ -97,6 +128,13 @@ impl TransientResourceCreator for Device {
                 }
                 .into()
             }
+            AnyTransientResourceDescriptor::TextureView(desc) => {
+                panic!("texture view {:?} can only be imported", desc.label)
+            }
         }
     }
+
+    fn device(&self) -> &Device {
+        self
+    }
 }
@@ -2,24 +2,42 @@ mod render_pass;
 
 pub use render_pass::*;
 
-use wgpu::{CommandBuffer, CommandEncoder, CommandEncoderDescriptor, Device, RenderPipeline};
+use wgpu::{
+    BindGroup, CommandBuffer, CommandEncoder, CommandEncoderDescriptor, Device, RenderBundle,
+    RenderPipeline, TextureView,
+};
 
-use crate::{PipelineContainer, ResourceRef, ResourceTable, ResourceView, TransientResource};
+use crate::{
+    CaptureObjects, CapturedPassCommand, ErrorScope, IndexHandle, PassErrorStage, PassStats,
+    PendingErrorScope, PipelineContainer, ResourceNode, ResourceRef, ResourceTable, ResourceView,
+    TransientBindGroup, TransientResource, TransientResourceCache, TransientResourceCreator,
+    TransientTexture, TransientTextureViewDescriptor,
+};
 
 pub struct PassContext<'a> {
-    device: &'a Device,
+    resource_creator: &'a dyn TransientResourceCreator,
     command_encoder: CommandEncoder,
     resource_table: &'a ResourceTable,
     pipeline_container: &'a PipelineContainer,
+    transient_resource_cache: &'a mut TransientResourceCache,
+    stats: PassStats,
 }
 
 impl PassContext<'_> {
+    pub fn record_draw(&mut self) {
+        self.stats.draws += 1;
+    }
+
     pub fn resource_table(&self) -> &ResourceTable {
         self.resource_table
     }
 
     pub fn device(&self) -> &Device {
-        self.device
+        self.resource_creator.device()
+    }
+
+    pub fn resource_creator(&self) -> &dyn TransientResourceCreator {
+        self.resource_creator
     }
 
     pub fn get_render_pipeline(&self, id: usize) -> &RenderPipeline {
@@ -28,6 +46,39 @@ impl PassContext<'_> {
             .expect("render pipeline mut have")
     }
 
+    pub fn get_or_create_bind_group(&mut self, bind_group: &TransientBindGroup) -> BindGroup {
+        let key = bind_group.get_bind_group_key(self);
+
+        self.transient_resource_cache.get_or_create_bind_group(
+            key,
+            bind_group.label.clone(),
+            self.resource_creator,
+        )
+    }
+
+    pub fn get_or_create_texture_view<ViewType: ResourceView>(
+        &mut self,
+        texture_ref: &ResourceRef<TransientTexture, ViewType>,
+        desc: &TransientTextureViewDescriptor,
+    ) -> TextureView {
+        let texture = self.resource_table.get_resource(texture_ref);
+
+        self.transient_resource_cache
+            .get_or_create_texture_view(&texture.resource, desc)
+    }
+
+    pub fn get_or_create_render_bundle(
+        &mut self,
+        desc: &TransientRenderBundleDescriptor,
+        commands: Vec<RenderBundleCommand>,
+    ) -> RenderBundle {
+        self.transient_resource_cache.get_or_create_render_bundle(
+            desc,
+            commands,
+            self.resource_creator,
+        )
+    }
+
     pub fn finish(self) -> CommandBuffer {
         self.command_encoder.finish()
     }
@@ -42,6 +93,10 @@ impl PassContext<'_> {
 
 pub trait PassCommand: 'static + Send + Sync {
     fn execute(&self, context: &mut PassContext);
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedPassCommand {
+        CapturedPassCommand::Unsupported(std::any::type_name::<Self>().to_string())
+    }
 }
 
 #[derive(Default)]
@@ -55,27 +110,106 @@ impl Pass {
         self.commands.push(Box::new(value));
     }
 
+    pub fn capture(&self, objects: &mut CaptureObjects) -> Vec<CapturedPassCommand> {
+        self.commands
+            .iter()
+            .map(|command| command.capture(objects))
+            .collect()
+    }
+
     pub fn render(
         &self,
         command_buffers: &mut Vec<CommandBuffer>,
-        device: &Device,
+        resource_creator: &dyn TransientResourceCreator,
+        resource_table: &ResourceTable,
+        pipeline_container: &PipelineContainer,
+        transient_resource_cache: &mut TransientResourceCache,
+    ) -> PassStats {
+        let command_encoder =
+            resource_creator
+                .device()
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: self.label.as_deref(),
+                });
+
+        let mut pass_context = PassContext {
+            resource_creator,
+            command_encoder,
+            resource_table,
+            pipeline_container,
+            transient_resource_cache,
+            stats: PassStats::default(),
+        };
+
+        for command in self.commands.iter() {
+            command.execute(&mut pass_context);
+        }
+        let stats = std::mem::take(&mut pass_context.stats);
+        command_buffers.push(pass_context.finish());
+
+        stats
+    }
+
+    pub(crate) fn render_with_error_scopes(
+        &self,
+        command_buffers: &mut Vec<CommandBuffer>,
+        resource_creator: &dyn TransientResourceCreator,
         resource_table: &ResourceTable,
         pipeline_container: &PipelineContainer,
-    ) {
+        transient_resource_cache: &mut TransientResourceCache,
+    ) -> (PassStats, Vec<ScopedPassErrors>) {
+        let device = resource_creator.device();
         let command_encoder = device.create_command_encoder(&CommandEncoderDescriptor {
             label: self.label.as_deref(),
         });
 
         let mut pass_context = PassContext {
-            device,
+            resource_creator,
             command_encoder,
             resource_table,
             pipeline_container,
+            transient_resource_cache,
+            stats: PassStats::default(),
         };
 
-        for command in self.commands.iter() {
+        let mut scoped_errors = vec![];
+        let mut pass_resources = vec![];
+        for (index, command) in self.commands.iter().enumerate() {
+            let error_scope = ErrorScope::push(device);
+            resource_table.begin_access_tracking();
+
             command.execute(&mut pass_context);
+
+            let resources = resource_table.end_access_tracking();
+            for resource in resources.iter() {
+                if !pass_resources.contains(resource) {
+                    pass_resources.push(*resource);
+                }
+            }
+
+            scoped_errors.push(ScopedPassErrors {
+                stage: PassErrorStage::Command(index),
+                resources,
+                scope: error_scope.pop(),
+            });
         }
+
+        let stats = std::mem::take(&mut pass_context.stats);
+
+        let error_scope = ErrorScope::push(device);
         command_buffers.push(pass_context.finish());
+        scoped_errors.push(ScopedPassErrors {
+            stage: PassErrorStage::Finish,
+            resources: pass_resources,
+            scope: error_scope.pop(),
+        });
+
+        (stats, scoped_errors)
     }
 }
+
+pub(crate) struct ScopedPassErrors {
+    pub stage: PassErrorStage,
+    pub resources: Vec<IndexHandle<ResourceNode>>,
+    pub scope: PendingErrorScope,
+}
@@ -3,46 +3,92 @@ use std::ops::Range;
 use wgpu::{IndexFormat, RenderPipeline};
 
 use crate::{
-    GpuRenderPass, PassContext, ResourceRead, ResourceRef, TransientBindGroup, TransientBuffer,
+    GpuRenderPass, PassContext, RenderBundleCommand, ResourceRead, ResourceRef, TransientBindGroup,
+    TransientBuffer, TransientRenderBundle,
 };
 
+enum RenderPassEncoder {
+    RenderPass(Box<GpuRenderPass>),
+    RenderBundle(Vec<RenderBundleCommand>),
+}
+
 pub struct RenderPassContext<'a, 'b> {
-    render_pass: GpuRenderPass,
+    encoder: RenderPassEncoder,
     pass_context: &'b mut PassContext<'a>,
 }
 
 impl<'a, 'b> RenderPassContext<'a, 'b> {
     pub fn new(render_pass: GpuRenderPass, pass_context: &'b mut PassContext<'a>) -> Self {
         RenderPassContext {
-            render_pass,
+            encoder: RenderPassEncoder::RenderPass(Box::new(render_pass)),
             pass_context,
         }
     }
 
     pub fn set_bind_group(&mut self, index: u32, bind_group: &TransientBindGroup, offsets: &[u32]) {
-        let bind_group = bind_group.create_bind_group(self.pass_context);
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                let bind_group = self.pass_context.get_or_create_bind_group(bind_group);
 
-        self.render_pass
-            .get_render_pass_mut()
-            .set_bind_group(index, &bind_group, offsets);
+                render_pass
+                    .get_render_pass_mut()
+                    .set_bind_group(index, &bind_group, offsets);
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::SetBindGroup {
+                    index,
+                    key: bind_group.get_bind_group_key(self.pass_context),
+                    label: bind_group.label.clone(),
+                    offsets: offsets.to_vec(),
+                });
+            }
+        }
     }
 
     pub fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
-        self.render_pass
-            .get_render_pass_mut()
-            .draw_indexed(indices, base_vertex, instances);
+        self.pass_context.record_draw();
+
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                render_pass
+                    .get_render_pass_mut()
+                    .draw_indexed(indices, base_vertex, instances);
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::DrawIndexed {
+                    indices,
+                    base_vertex,
+                    instances,
+                });
+            }
+        }
     }
 
     pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
-        self.render_pass
-            .get_render_pass_mut()
-            .draw(vertices, instances);
+        self.pass_context.record_draw();
+
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                render_pass.get_render_pass_mut().draw(vertices, instances);
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::Draw {
+                    vertices,
+                    instances,
+                });
+            }
+        }
     }
 
     pub fn set_render_pipeline(&mut self, pipeline: &RenderPipeline) {
-        self.render_pass
-            .get_render_pass_mut()
-            .set_pipeline(pipeline);
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                render_pass.get_render_pass_mut().set_pipeline(pipeline);
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::SetRenderPipeline(pipeline.clone()));
+            }
+        }
     }
 
     pub fn set_vertex_buffer(
@@ -53,9 +99,22 @@ impl<'a, 'b> RenderPassContext<'a, 'b> {
         size: u64,
     ) {
         let buffer = self.pass_context.resource_table.get_resource(buffer_ref);
-        self.render_pass
-            .get_render_pass_mut()
-            .set_vertex_buffer(slot, buffer.resource.slice(offset..(offset + size)));
+
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                render_pass
+                    .get_render_pass_mut()
+                    .set_vertex_buffer(slot, buffer.resource.slice(offset..(offset + size)));
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::SetVertexBuffer {
+                    slot,
+                    buffer: buffer.resource.clone(),
+                    offset,
+                    size,
+                });
+            }
+        }
     }
 
     pub fn set_index_buffer(
@@ -67,8 +126,53 @@ impl<'a, 'b> RenderPassContext<'a, 'b> {
     ) {
         let buffer = self.pass_context.resource_table.get_resource(buffer_ref);
 
-        self.render_pass
-            .get_render_pass_mut()
-            .set_index_buffer(buffer.resource.slice(offset..(offset + size)), index_format);
+        match &mut self.encoder {
+            RenderPassEncoder::RenderPass(render_pass) => {
+                render_pass
+                    .get_render_pass_mut()
+                    .set_index_buffer(buffer.resource.slice(offset..(offset + size)), index_format);
+            }
+            RenderPassEncoder::RenderBundle(commands) => {
+                commands.push(RenderBundleCommand::SetIndexBuffer {
+                    buffer: buffer.resource.clone(),
+                    index_format,
+                    offset,
+                    size,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn execute_bundle(&mut self, bundle: &TransientRenderBundle) {
+        if let RenderPassEncoder::RenderBundle(_) = &self.encoder {
+            for command in bundle.commands.iter() {
+                command.execute(self);
+            }
+
+            return;
+        }
+
+        let mut bundle_context = RenderPassContext {
+            encoder: RenderPassEncoder::RenderBundle(vec![]),
+            pass_context: &mut *self.pass_context,
+        };
+
+        for command in bundle.commands.iter() {
+            command.execute(&mut bundle_context);
+        }
+
+        let RenderPassEncoder::RenderBundle(commands) = bundle_context.encoder else {
+            unreachable!()
+        };
+
+        let render_bundle = self
+            .pass_context
+            .get_or_create_render_bundle(&bundle.desc, commands);
+
+        if let RenderPassEncoder::RenderPass(render_pass) = &mut self.encoder {
+            render_pass
+                .get_render_pass_mut()
+                .execute_bundles([&render_bundle]);
+        }
     }
 }
@@ -1,12 +1,18 @@
 mod context;
 mod gpu_render_pass;
+mod render_bundle;
 
 pub use context::*;
 pub use gpu_render_pass::*;
+pub use render_bundle::*;
 
 use wgpu::{Color, Operations};
 
-use crate::{PassCommand, PassContext, TextureViewEdge};
+use crate::{
+    CaptureObjects, CapturedColorAttachment, CapturedDepthStencilAttachment, CapturedPassCommand,
+    CapturedRenderPass, CapturedRenderPassCommand, CapturedTextureView, PassCommand, PassContext,
+    TextureViewEdge,
+};
 
 pub struct TransientRenderPassColorAttachment {
     pub view: TextureViewEdge,
@@ -18,7 +24,7 @@ pub struct TransientRenderPassColorAttachment {
 impl TransientRenderPassColorAttachment {
     pub fn create_render_pass_color_attachment(
         &self,
-        context: &PassContext,
+        context: &mut PassContext,
     ) -> RenderPassColorAttachment {
         RenderPassColorAttachment {
             view: self.view.create_texture_view(context),
@@ -41,7 +47,7 @@ pub struct TransientRenderPassDepthStencilAttachment {
 impl TransientRenderPassDepthStencilAttachment {
     pub fn create_render_pass_depth_stencil_attachment(
         &self,
-        context: &PassContext,
+        context: &mut PassContext,
     ) -> RenderPassDepthStencilAttachment {
         RenderPassDepthStencilAttachment {
             view: self.view.create_texture_view(context),
@@ -59,7 +65,7 @@ pub struct TransientRenderPassDescriptor {
 }
 
 impl TransientRenderPassDescriptor {
-    pub fn create_render_pass_descriptor(&self, context: &PassContext) -> RenderPassDescriptor {
+    pub fn create_render_pass_descriptor(&self, context: &mut PassContext) -> RenderPassDescriptor {
         RenderPassDescriptor {
             label: self.label.clone(),
             color_attachments: self
@@ -82,11 +88,15 @@ impl TransientRenderPassDescriptor {
 
 pub trait RenderPassCommand: Sync + Send + 'static {
     fn execute(&self, render_pass_context: &mut RenderPassContext);
+
+    fn capture(&self, _objects: &mut CaptureObjects) -> CapturedRenderPassCommand {
+        CapturedRenderPassCommand::Unsupported(std::any::type_name::<Self>().to_string())
+    }
 }
 
 #[derive(Default)]
 pub struct RenderPass {
-    desc: TransientRenderPassDescriptor,
+    pub(crate) desc: TransientRenderPassDescriptor,
     pub(crate) commands: Vec<Box<dyn RenderPassCommand>>,
 }
 
@@ -113,4 +123,45 @@ impl PassCommand for RenderPass {
             command.execute(&mut render_pass_context);
         }
     }
+
+    fn capture(&self, objects: &mut CaptureObjects) -> CapturedPassCommand {
+        let color_attachments = self
+            .desc
+            .color_attachments
+            .iter()
+            .map(|color_attachment| {
+                color_attachment
+                    .as_ref()
+                    .map(|color_attachment| CapturedColorAttachment {
+                        view: CapturedTextureView::new(&color_attachment.view, objects),
+                        depth_slice: color_attachment.depth_slice,
+                        resolve_target: color_attachment.resolve_target.as_ref().map(
+                            |resolve_target| CapturedTextureView::new(resolve_target, objects),
+                        ),
+                        ops: color_attachment.ops,
+                    })
+            })
+            .collect();
+
+        let depth_stencil_attachment =
+            self.desc
+                .depth_stencil_attachment
+                .as_ref()
+                .map(|depth_stencil_attachment| CapturedDepthStencilAttachment {
+                    view: CapturedTextureView::new(&depth_stencil_attachment.view, objects),
+                    depth_ops: depth_stencil_attachment.depth_ops,
+                    stencil_ops: depth_stencil_attachment.stencil_ops,
+                });
+
+        CapturedPassCommand::RenderPass(CapturedRenderPass {
+            label: self.desc.label.clone(),
+            color_attachments,
+            depth_stencil_attachment,
+            commands: self
+                .commands
+                .iter()
+                .map(|command| command.capture(objects))
+                .collect(),
+        })
+    }
 }
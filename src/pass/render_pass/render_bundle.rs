@@ -0,0 +1,147 @@
+use std::{num::NonZeroU32, ops::Range};
+
+use wgpu::{
+    BindGroup, Buffer, Device, IndexFormat, RenderBundle, RenderBundleDepthStencil,
+    RenderBundleDescriptor, RenderBundleEncoderDescriptor, RenderPipeline, Texture, TextureFormat,
+};
+
+use crate::{BindGroupKey, RenderPassCommand};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransientRenderBundleDescriptor {
+    pub key: String,
+    pub color_formats: Vec<Option<TextureFormat>>,
+    pub depth_stencil: Option<RenderBundleDepthStencil>,
+    pub sample_count: u32,
+    pub multiview: Option<NonZeroU32>,
+}
+
+impl TransientRenderBundleDescriptor {
+    pub fn get_desc(&self) -> RenderBundleEncoderDescriptor<'_> {
+        RenderBundleEncoderDescriptor {
+            label: Some(&self.key),
+            color_formats: &self.color_formats,
+            depth_stencil: self.depth_stencil,
+            sample_count: self.sample_count,
+            multiview: self.multiview,
+        }
+    }
+}
+
+pub struct TransientRenderBundle {
+    pub desc: TransientRenderBundleDescriptor,
+    pub(crate) commands: Vec<Box<dyn RenderPassCommand>>,
+}
+
+impl TransientRenderBundle {
+    pub fn new(desc: TransientRenderBundleDescriptor) -> Self {
+        Self {
+            desc,
+            commands: vec![],
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum RenderBundleCommand {
+    SetBindGroup {
+        index: u32,
+        key: BindGroupKey,
+        label: Option<String>,
+        offsets: Vec<u32>,
+    },
+    SetRenderPipeline(RenderPipeline),
+    SetVertexBuffer {
+        slot: u32,
+        buffer: Buffer,
+        offset: u64,
+        size: u64,
+    },
+    SetIndexBuffer {
+        buffer: Buffer,
+        index_format: IndexFormat,
+        offset: u64,
+        size: u64,
+    },
+    Draw {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    DrawIndexed {
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    },
+}
+
+impl RenderBundleCommand {
+    pub fn references_buffer(&self, buffer: &Buffer) -> bool {
+        match self {
+            RenderBundleCommand::SetBindGroup { key, .. } => key.references_buffer(buffer),
+            RenderBundleCommand::SetVertexBuffer { buffer: target, .. } => target == buffer,
+            RenderBundleCommand::SetIndexBuffer { buffer: target, .. } => target == buffer,
+            _ => false,
+        }
+    }
+
+    pub fn references_texture(&self, texture: &Texture) -> bool {
+        match self {
+            RenderBundleCommand::SetBindGroup { key, .. } => key.references_texture(texture),
+            _ => false,
+        }
+    }
+}
+
+pub fn create_render_bundle(
+    device: &Device,
+    desc: &TransientRenderBundleDescriptor,
+    commands: &[RenderBundleCommand],
+    bind_groups: &[Option<BindGroup>],
+) -> RenderBundle {
+    let mut encoder = device.create_render_bundle_encoder(&desc.get_desc());
+
+    for (command, bind_group) in commands.iter().zip(bind_groups.iter()) {
+        match command {
+            RenderBundleCommand::SetBindGroup { index, offsets, .. } => {
+                encoder.set_bind_group(*index, bind_group.as_ref(), offsets);
+            }
+            RenderBundleCommand::SetRenderPipeline(pipeline) => {
+                encoder.set_pipeline(pipeline);
+            }
+            RenderBundleCommand::SetVertexBuffer {
+                slot,
+                buffer,
+                offset,
+                size,
+            } => {
+                encoder.set_vertex_buffer(*slot, buffer.slice(*offset..(*offset + *size)));
+            }
+            RenderBundleCommand::SetIndexBuffer {
+                buffer,
+                index_format,
+                offset,
+                size,
+            } => {
+                encoder.set_index_buffer(buffer.slice(*offset..(*offset + *size)), *index_format);
+            }
+            RenderBundleCommand::Draw {
+                vertices,
+                instances,
+            } => {
+                encoder.draw(vertices.clone(), instances.clone());
+            }
+            RenderBundleCommand::DrawIndexed {
+                indices,
+                base_vertex,
+                instances,
+            } => {
+                encoder.draw_indexed(indices.clone(), *base_vertex, instances.clone());
+            }
+        }
+    }
+
+    encoder.finish(&RenderBundleDescriptor {
+        label: Some(&desc.key),
+    })
+}
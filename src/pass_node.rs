@@ -1,13 +1,16 @@
-use crate::{IndexHandle, Pass, RawResourceHandle, ResourceNode};
+use crate::{IndexHandle, Pass, RawResourceHandle, ResourceNode, ResourceRange};
 
 pub struct PassNode {
     pub name: String,
     pub index: IndexHandle<PassNode>,
     pub writes: Vec<RawResourceHandle>,
     pub reads: Vec<RawResourceHandle>,
+    pub read_ranges: Vec<(IndexHandle<ResourceNode>, Option<ResourceRange>)>,
     pub resource_request_array: Vec<IndexHandle<ResourceNode>>,
     pub resource_release_array: Vec<IndexHandle<ResourceNode>>,
     pub pass: Option<Pass>,
+    pub culled: bool,
+    pub dependencies: Vec<String>,
 }
 
 impl PassNode {
@@ -17,9 +20,12 @@ impl PassNode {
             index,
             writes: Default::default(),
             reads: Default::default(),
+            read_ranges: Default::default(),
             resource_request_array: Default::default(),
             resource_release_array: Default::default(),
             pass: Default::default(),
+            culled: false,
+            dependencies: Default::default(),
         }
     }
 }
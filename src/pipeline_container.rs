@@ -25,6 +25,7 @@ pub trait GetPipelineContainer {
     fn get_pipeline_container(&self) -> PipelineContainer;
 }
 
+#[derive(Default)]
 pub struct PipelineContainer(Vec<Option<Pipeline>>);
 
 impl PipelineContainer {
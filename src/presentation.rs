@@ -0,0 +1,336 @@
+use std::{fmt, sync::Arc};
+
+use wgpu::{
+    Device, Extent3d, Queue, SubmissionIndex, Surface, SurfaceConfiguration, SurfaceError,
+    SurfaceTexture, TextureDimension, TextureFormat, TextureUsages,
+};
+
+use crate::{
+    FrameGraph, FrameGraphContext, ResourceHandle, TransientTexture, TransientTextureDescriptor,
+    TransientTextureViewDescriptor,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresentationError {
+    IncompatibleViewFormat {
+        format: TextureFormat,
+        view_format: TextureFormat,
+    },
+}
+
+impl fmt::Display for PresentationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresentationError::IncompatibleViewFormat {
+                format,
+                view_format,
+            } => write!(
+                f,
+                "presentation view format {view_format:?} is not compatible with {format:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PresentationError {}
+
+pub trait PresentationTarget {
+    fn acquire(&mut self) -> Option<Arc<TransientTexture>>;
+
+    fn view_format(&self) -> TextureFormat;
+
+    fn present(&mut self, submission: SubmissionIndex);
+}
+
+pub struct PresentationTargetHandle {
+    pub texture: ResourceHandle<TransientTexture>,
+    pub view_desc: TransientTextureViewDescriptor,
+}
+
+impl FrameGraph {
+    pub fn import_presentation_target<T: PresentationTarget>(
+        &mut self,
+        name: &str,
+        target: &mut T,
+    ) -> Option<PresentationTargetHandle> {
+        let texture = target.acquire()?;
+        let texture = self.import(name, texture);
+        self.mark_output(&texture);
+
+        Some(PresentationTargetHandle {
+            texture,
+            view_desc: TransientTextureViewDescriptor {
+                label: Some(name.to_string()),
+                format: Some(target.view_format()),
+                ..Default::default()
+            },
+        })
+    }
+}
+
+impl FrameGraphContext<'_> {
+    pub fn submit_and_present<T: PresentationTarget>(
+        self,
+        queue: &Queue,
+        target: &mut T,
+    ) -> SubmissionIndex {
+        let submission = self.submit(queue);
+        target.present(submission.clone());
+
+        submission
+    }
+}
+
+fn get_view_formats(
+    format: TextureFormat,
+    view_format: TextureFormat,
+) -> Result<Vec<TextureFormat>, PresentationError> {
+    if format.remove_srgb_suffix() != view_format.remove_srgb_suffix() {
+        return Err(PresentationError::IncompatibleViewFormat {
+            format,
+            view_format,
+        });
+    }
+
+    if format == view_format {
+        Ok(vec![])
+    } else {
+        Ok(vec![view_format])
+    }
+}
+
+pub struct SurfacePresentationTarget {
+    device: Device,
+    surface: Surface<'static>,
+    config: SurfaceConfiguration,
+    view_format: TextureFormat,
+    current: Option<SurfaceTexture>,
+}
+
+impl SurfacePresentationTarget {
+    pub fn new(
+        device: &Device,
+        surface: Surface<'static>,
+        mut config: SurfaceConfiguration,
+        view_format: TextureFormat,
+    ) -> Result<Self, PresentationError> {
+        for format in get_view_formats(config.format, view_format)? {
+            if !config.view_formats.contains(&format) {
+                config.view_formats.push(format);
+            }
+        }
+        surface.configure(device, &config);
+
+        Ok(Self {
+            device: device.clone(),
+            surface,
+            config,
+            view_format,
+            current: None,
+        })
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.config.width = width.max(1);
+        self.config.height = height.max(1);
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    pub fn config(&self) -> &SurfaceConfiguration {
+        &self.config
+    }
+}
+
+impl PresentationTarget for SurfacePresentationTarget {
+    fn acquire(&mut self) -> Option<Arc<TransientTexture>> {
+        let surface_texture = match self.surface.get_current_texture() {
+            Ok(surface_texture) => surface_texture,
+            Err(SurfaceError::Outdated | SurfaceError::Lost) => {
+                self.surface.configure(&self.device, &self.config);
+                self.surface.get_current_texture().ok()?
+            }
+            Err(_) => return None,
+        };
+
+        let texture = Arc::new(TransientTexture {
+            resource: surface_texture.texture.clone(),
+            desc: TransientTextureDescriptor {
+                view_formats: self.config.view_formats.clone(),
+                ..TransientTextureDescriptor::from_texture(&surface_texture.texture)
+            },
+        });
+        self.current = Some(surface_texture);
+
+        Some(texture)
+    }
+
+    fn view_format(&self) -> TextureFormat {
+        self.view_format
+    }
+
+    fn present(&mut self, _submission: SubmissionIndex) {
+        if let Some(surface_texture) = self.current.take() {
+            surface_texture.present();
+        }
+    }
+}
+
+pub struct HeadlessPresentationTarget {
+    texture: Arc<TransientTexture>,
+    view_format: TextureFormat,
+    presented_frames: u64,
+    last_submission: Option<SubmissionIndex>,
+}
+
+impl HeadlessPresentationTarget {
+    pub fn new(
+        device: &Device,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        view_format: TextureFormat,
+    ) -> Result<Self, PresentationError> {
+        let desc = TransientTextureDescriptor {
+            label: Some("headless_presentation_target".to_string()),
+            size: Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: get_view_formats(format, view_format)?,
+        };
+
+        Ok(Self {
+            texture: Arc::new(TransientTexture {
+                resource: device.create_texture(&desc.get_desc()),
+                desc,
+            }),
+            view_format,
+            presented_frames: 0,
+            last_submission: None,
+        })
+    }
+
+    pub fn texture(&self) -> &Arc<TransientTexture> {
+        &self.texture
+    }
+
+    pub fn presented_frames(&self) -> u64 {
+        self.presented_frames
+    }
+
+    pub fn last_submission(&self) -> Option<&SubmissionIndex> {
+        self.last_submission.as_ref()
+    }
+}
+
+impl PresentationTarget for HeadlessPresentationTarget {
+    fn acquire(&mut self) -> Option<Arc<TransientTexture>> {
+        Some(self.texture.clone())
+    }
+
+    fn view_format(&self) -> TextureFormat {
+        self.view_format
+    }
+
+    fn present(&mut self, submission: SubmissionIndex) {
+        self.presented_frames += 1;
+        self.last_submission = Some(submission);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{Color, LoadOp, Operations, StoreOp, TextureFormat};
+
+    use crate::{
+        FrameGraph, FrameGraphContext, HeadlessPresentationTarget, PassNodeBuilderExt,
+        PipelineContainer, PresentationError, TextureViewEdge, TransientRenderPassColorAttachment,
+        TransientResourceCache, TransientTextureView,
+    };
+
+    #[test]
+    fn headless_presentation_target_presents_after_submission() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut transient_resource_cache = TransientResourceCache::default();
+        let mut target = HeadlessPresentationTarget::new(
+            &device,
+            64,
+            32,
+            TextureFormat::Rgba8Unorm,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+        .unwrap();
+        assert_eq!(
+            target.texture().desc.view_formats,
+            vec![TextureFormat::Rgba8UnormSrgb]
+        );
+
+        let mut frame_graph = FrameGraph::default();
+        let surface = frame_graph
+            .import_presentation_target("surface", &mut target)
+            .unwrap();
+
+        {
+            let mut builder = frame_graph.create_pass_buidlder("present");
+            let texture = builder.write(surface.texture);
+            builder
+                .create_render_pass_builder("present")
+                .add_color_attachment(TransientRenderPassColorAttachment {
+                    view: TextureViewEdge::Write(TransientTextureView {
+                        texture,
+                        desc: surface.view_desc,
+                    }),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::BLACK),
+                        store: StoreOp::Store,
+                    },
+                });
+        }
+
+        frame_graph.compile().unwrap();
+
+        let mut context = FrameGraphContext::new(
+            PipelineContainer::default(),
+            &device,
+            &mut transient_resource_cache,
+        );
+        frame_graph.execute(&mut context);
+        assert_eq!(context.stats().passes_executed, 1);
+        assert_eq!(target.presented_frames(), 0);
+
+        context.submit_and_present(&queue, &mut target);
+        assert_eq!(target.presented_frames(), 1);
+        assert!(target.last_submission().is_some());
+    }
+
+    #[test]
+    fn headless_presentation_target_rejects_incompatible_view_formats() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+
+        let result = HeadlessPresentationTarget::new(
+            &device,
+            64,
+            32,
+            TextureFormat::Rgba8Unorm,
+            TextureFormat::Bgra8UnormSrgb,
+        );
+
+        assert_eq!(
+            result.err(),
+            Some(PresentationError::IncompatibleViewFormat {
+                format: TextureFormat::Rgba8Unorm,
+                view_format: TextureFormat::Bgra8UnormSrgb,
+            })
+        );
+    }
+}
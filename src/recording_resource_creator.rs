@@ -0,0 +1,70 @@
+use std::sync::Mutex;
+
+use wgpu::{BindGroup, Device};
+
+use crate::{
+    AnyTransientResource, AnyTransientResourceDescriptor, TransientBindGroupDescriptor,
+    TransientResourceCreator,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceCreatorRecord {
+    Resource(AnyTransientResourceDescriptor),
+    BindGroup(Option<String>),
+}
+
+pub struct RecordingResourceCreator<ResourceCreator = Device> {
+    inner: ResourceCreator,
+    records: Mutex<Vec<ResourceCreatorRecord>>,
+}
+
+impl<ResourceCreator: TransientResourceCreator> RecordingResourceCreator<ResourceCreator> {
+    pub fn new(inner: ResourceCreator) -> Self {
+        Self {
+            inner,
+            records: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn records(&self) -> Vec<ResourceCreatorRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    pub fn created_resources(&self) -> Vec<AnyTransientResourceDescriptor> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|record| match record {
+                ResourceCreatorRecord::Resource(desc) => Some(desc.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+
+    fn record(&self, record: ResourceCreatorRecord) {
+        self.records.lock().unwrap().push(record);
+    }
+}
+
+impl<ResourceCreator: TransientResourceCreator> TransientResourceCreator
+    for RecordingResourceCreator<ResourceCreator>
+{
+    fn create_resource(&self, desc: &AnyTransientResourceDescriptor) -> AnyTransientResource {
+        self.record(ResourceCreatorRecord::Resource(desc.clone()));
+        self.inner.create_resource(desc)
+    }
+
+    fn create_bind_group(&self, desc: &TransientBindGroupDescriptor) -> BindGroup {
+        self.record(ResourceCreatorRecord::BindGroup(desc.label.clone()));
+        self.inner.create_bind_group(desc)
+    }
+
+    fn device(&self) -> &Device {
+        self.inner.device()
+    }
+}
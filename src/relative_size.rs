@@ -0,0 +1,97 @@
+use std::fmt;
+
+use wgpu::Extent3d;
+
+use crate::{IndexHandle, ResourceNode, TransientTextureDescriptor};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SizeRounding {
+    Floor,
+    #[default]
+    Ceil,
+    Round,
+}
+
+impl SizeRounding {
+    pub fn apply(&self, value: u32, scale: f32) -> u32 {
+        let scaled = value as f32 * scale;
+
+        let rounded = match self {
+            SizeRounding::Floor => scaled.floor(),
+            SizeRounding::Ceil => scaled.ceil(),
+            SizeRounding::Round => scaled.round(),
+        };
+
+        (rounded as u32).max(1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RelativeSizeSource {
+    Reference,
+    Resource(IndexHandle<ResourceNode>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RelativeSize {
+    pub source: RelativeSizeSource,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub rounding: SizeRounding,
+}
+
+impl RelativeSize {
+    pub fn reference(scale: f32) -> Self {
+        RelativeSize {
+            source: RelativeSizeSource::Reference,
+            scale_x: scale,
+            scale_y: scale,
+            rounding: SizeRounding::default(),
+        }
+    }
+
+    pub fn resource(resource: IndexHandle<ResourceNode>, scale: f32) -> Self {
+        RelativeSize {
+            source: RelativeSizeSource::Resource(resource),
+            scale_x: scale,
+            scale_y: scale,
+            rounding: SizeRounding::default(),
+        }
+    }
+
+    pub fn with_rounding(mut self, rounding: SizeRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn resolve(&self, source_size: Extent3d, depth_or_array_layers: u32) -> Extent3d {
+        Extent3d {
+            width: self.rounding.apply(source_size.width, self.scale_x),
+            height: self.rounding.apply(source_size.height, self.scale_y),
+            depth_or_array_layers,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RelativeTextureDescriptor {
+    pub desc: TransientTextureDescriptor,
+    pub size: RelativeSize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelativeSizeError {
+    SourceNotTexture { source: String },
+}
+
+impl fmt::Display for RelativeSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelativeSizeError::SourceNotTexture { source } => {
+                write!(f, "relative size source {source} is not a texture")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RelativeSizeError {}
@@ -1,6 +1,9 @@
 use core::marker::PhantomData;
 
-use crate::{IndexHandle, PassNode, TransientResource, VirtualResource};
+use crate::{
+    IndexHandle, PassNode, RelativeSize, ResourceRange, ResourceRangeWrite, TransientResource,
+    VirtualResource, resource_range::resolve_read_versions,
+};
 
 pub struct ResourceRef<ResourceType: TransientResource, VieType> {
     pub raw: RawResourceHandle,
@@ -41,14 +44,21 @@ impl<ResourceType: TransientResource, VieType> Eq for ResourceRef<ResourceType,
 
 pub trait ResourceView {}
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct ResourceRead;
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct ResourceWrite;
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResourceReadWrite;
+
 impl ResourceView for ResourceRead {}
 
 impl ResourceView for ResourceWrite {}
 
+impl ResourceView for ResourceReadWrite {}
+
 pub struct ResourceHandle<ResourceType: TransientResource> {
     pub raw: RawResourceHandle,
     pub desc: <ResourceType as TransientResource>::Descriptor,
@@ -79,7 +89,21 @@ impl<ResourceType: TransientResource> ResourceHandle<ResourceType> {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct HistoryResourceHandle<ResourceType: TransientResource> {
+    pub previous: ResourceHandle<ResourceType>,
+    pub current: ResourceHandle<ResourceType>,
+}
+
+impl<ResourceType: TransientResource> Clone for HistoryResourceHandle<ResourceType> {
+    fn clone(&self) -> Self {
+        HistoryResourceHandle {
+            previous: self.previous.clone(),
+            current: self.current.clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct RawResourceHandle {
     pub index: IndexHandle<ResourceNode>,
     pub version: u32,
@@ -92,15 +116,23 @@ pub struct ResourceNode {
     pub last_user_pass: Option<IndexHandle<PassNode>>,
     version: u32,
     pub resource: VirtualResource,
+    pub exported: bool,
+    pub output: bool,
+    pub relative_size: Option<RelativeSize>,
+    pub contents: Vec<u8>,
+    pub range_writes: Vec<ResourceRangeWrite>,
 }
 
 pub struct ResourceRequese {
     pub index: IndexHandle<ResourceNode>,
+    pub name: String,
     pub resource: VirtualResource,
+    pub contents: Vec<u8>,
 }
 
 pub struct ResourceRelease {
     pub index: IndexHandle<ResourceNode>,
+    pub exported: bool,
 }
 
 impl ResourceNode {
@@ -112,6 +144,11 @@ impl ResourceNode {
             first_use_pass: None,
             last_user_pass: None,
             resource,
+            exported: false,
+            output: false,
+            relative_size: None,
+            contents: vec![],
+            range_writes: vec![],
         }
     }
 }
@@ -120,7 +157,9 @@ impl ResourceNode {
     pub fn request(&self) -> ResourceRequese {
         ResourceRequese {
             index: self.index,
+            name: self.name.clone(),
             resource: self.resource.clone(),
+            contents: self.contents.clone(),
         }
     }
 
@@ -134,7 +173,19 @@ impl ResourceNode {
     }
 
     pub fn release(&self) -> ResourceRelease {
-        ResourceRelease { index: self.index }
+        ResourceRelease {
+            index: self.index,
+            exported: self.exported,
+        }
+    }
+
+    pub fn is_output(&self) -> bool {
+        self.output
+            || self.exported
+            || matches!(
+                self.resource,
+                VirtualResource::Imported(_) | VirtualResource::History(_)
+            )
     }
 
     pub fn version(&self) -> u32 {
@@ -145,6 +196,20 @@ impl ResourceNode {
         self.version += 1;
     }
 
+    pub fn write_range(&mut self, range: Option<ResourceRange>) -> u32 {
+        self.new_version();
+        self.range_writes.push(ResourceRangeWrite {
+            range,
+            version: self.version,
+        });
+
+        self.version
+    }
+
+    pub fn read_versions(&self, range: Option<&ResourceRange>, version: u32) -> Vec<u32> {
+        resolve_read_versions(&self.range_writes, range, version)
+    }
+
     pub fn update_lifetime(&mut self, handle: IndexHandle<PassNode>) {
         if self.first_use_pass.is_none() {
             self.first_use_pass = Some(handle);
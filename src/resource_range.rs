@@ -0,0 +1,277 @@
+use std::ops::Range;
+
+use wgpu::TextureDimension;
+
+use crate::{
+    AnyTransientResourceDescriptor, TransientTextureDescriptor, TransientTextureViewDescriptor,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextureSubresourceRange {
+    pub mip_levels: Range<u32>,
+    pub array_layers: Range<u32>,
+}
+
+impl TextureSubresourceRange {
+    pub fn full(texture_desc: &TransientTextureDescriptor) -> Self {
+        TextureSubresourceRange {
+            mip_levels: 0..texture_desc.mip_level_count,
+            array_layers: 0..Self::array_layer_count(texture_desc),
+        }
+    }
+
+    pub fn from_view_desc(
+        texture_desc: &TransientTextureDescriptor,
+        view_desc: &TransientTextureViewDescriptor,
+    ) -> Self {
+        let mip_level_count = view_desc.mip_level_count.unwrap_or(
+            texture_desc
+                .mip_level_count
+                .saturating_sub(view_desc.base_mip_level),
+        );
+        let array_layer_count = view_desc.array_layer_count.unwrap_or(
+            Self::array_layer_count(texture_desc).saturating_sub(view_desc.base_array_layer),
+        );
+
+        TextureSubresourceRange {
+            mip_levels: view_desc.base_mip_level..view_desc.base_mip_level + mip_level_count,
+            array_layers: view_desc.base_array_layer
+                ..view_desc.base_array_layer + array_layer_count,
+        }
+    }
+
+    pub fn overlaps(&self, other: &TextureSubresourceRange) -> bool {
+        self.mip_levels.start < other.mip_levels.end
+            && other.mip_levels.start < self.mip_levels.end
+            && self.array_layers.start < other.array_layers.end
+            && other.array_layers.start < self.array_layers.end
+    }
+
+    pub fn contains(&self, mip_level: u32, array_layer: u32) -> bool {
+        self.mip_levels.contains(&mip_level) && self.array_layers.contains(&array_layer)
+    }
+
+    fn array_layer_count(texture_desc: &TransientTextureDescriptor) -> u32 {
+        match texture_desc.dimension {
+            TextureDimension::D3 => 1,
+            _ => texture_desc.size.depth_or_array_layers,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BufferRange {
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl BufferRange {
+    pub fn fits(&self, buffer_size: u64) -> bool {
+        self.offset
+            .checked_add(self.size)
+            .is_some_and(|end| end <= buffer_size)
+    }
+
+    pub fn clamp(&self, buffer_size: u64) -> Self {
+        let offset = self.offset.min(buffer_size);
+
+        Self {
+            offset,
+            size: self.size.min(buffer_size - offset),
+        }
+    }
+
+    pub fn end(&self) -> u64 {
+        self.offset + self.size
+    }
+
+    pub fn overlaps(&self, other: &BufferRange) -> bool {
+        self.offset < other.end() && other.offset < self.end()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResourceRange {
+    Texture(TextureSubresourceRange),
+    Buffer(BufferRange),
+}
+
+impl ResourceRange {
+    pub fn full(desc: &AnyTransientResourceDescriptor) -> Option<Self> {
+        match desc {
+            AnyTransientResourceDescriptor::Buffer(desc) => {
+                Some(ResourceRange::Buffer(BufferRange {
+                    offset: 0,
+                    size: desc.size,
+                }))
+            }
+            AnyTransientResourceDescriptor::Texture(desc) => {
+                Some(ResourceRange::Texture(TextureSubresourceRange::full(desc)))
+            }
+            AnyTransientResourceDescriptor::TextureView(_) => None,
+        }
+    }
+
+    pub fn overlaps(&self, other: &ResourceRange) -> bool {
+        match (self, other) {
+            (ResourceRange::Texture(range), ResourceRange::Texture(other)) => range.overlaps(other),
+            (ResourceRange::Buffer(range), ResourceRange::Buffer(other)) => range.overlaps(other),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceRangeWrite {
+    pub range: Option<ResourceRange>,
+    pub version: u32,
+}
+
+enum UncoveredRange {
+    Whole,
+    Cells(Vec<(u32, u32)>),
+    Intervals(Vec<BufferRange>),
+}
+
+impl UncoveredRange {
+    fn new(range: Option<&ResourceRange>) -> Self {
+        match range {
+            None => UncoveredRange::Whole,
+            Some(ResourceRange::Texture(range)) => UncoveredRange::Cells(
+                range
+                    .mip_levels
+                    .clone()
+                    .flat_map(|mip_level| {
+                        range
+                            .array_layers
+                            .clone()
+                            .map(move |array_layer| (mip_level, array_layer))
+                    })
+                    .collect(),
+            ),
+            Some(ResourceRange::Buffer(range)) => UncoveredRange::Intervals(vec![range.clone()]),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            UncoveredRange::Whole => false,
+            UncoveredRange::Cells(cells) => cells.is_empty(),
+            UncoveredRange::Intervals(intervals) => intervals.is_empty(),
+        }
+    }
+
+    fn cover(&mut self, range: Option<&ResourceRange>) -> bool {
+        match (self, range) {
+            (uncovered, None) => {
+                let covered = !uncovered.is_empty();
+                *uncovered = UncoveredRange::Cells(vec![]);
+                covered
+            }
+            (UncoveredRange::Whole, Some(_)) => true,
+            (UncoveredRange::Cells(cells), Some(ResourceRange::Texture(range))) => {
+                let len = cells.len();
+                cells.retain(|(mip_level, array_layer)| !range.contains(*mip_level, *array_layer));
+                cells.len() != len
+            }
+            (UncoveredRange::Intervals(intervals), Some(ResourceRange::Buffer(range))) => {
+                let mut covered = false;
+                let mut remaining = vec![];
+
+                for interval in intervals.drain(..) {
+                    if !interval.overlaps(range) {
+                        remaining.push(interval);
+                        continue;
+                    }
+
+                    covered = true;
+
+                    if interval.offset < range.offset {
+                        remaining.push(BufferRange {
+                            offset: interval.offset,
+                            size: range.offset - interval.offset,
+                        });
+                    }
+
+                    if range.end() < interval.end() {
+                        remaining.push(BufferRange {
+                            offset: range.end(),
+                            size: interval.end() - range.end(),
+                        });
+                    }
+                }
+
+                *intervals = remaining;
+                covered
+            }
+            (_, Some(_)) => true,
+        }
+    }
+}
+
+pub(crate) fn resolve_read_versions(
+    writes: &[ResourceRangeWrite],
+    range: Option<&ResourceRange>,
+    version: u32,
+) -> Vec<u32> {
+    let mut uncovered = UncoveredRange::new(range);
+    let mut versions = vec![];
+
+    for write in writes.iter().rev().filter(|write| write.version <= version) {
+        if uncovered.is_empty() {
+            break;
+        }
+
+        if uncovered.cover(write.range.as_ref()) {
+            versions.push(write.version);
+        }
+    }
+
+    if versions.is_empty() {
+        versions.push(0);
+    }
+
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ResourceRange, ResourceRangeWrite, TextureSubresourceRange};
+
+    use super::resolve_read_versions;
+
+    fn mip(level: u32) -> Option<ResourceRange> {
+        Some(ResourceRange::Texture(TextureSubresourceRange {
+            mip_levels: level..level + 1,
+            array_layers: 0..6,
+        }))
+    }
+
+    #[test]
+    fn resolve_read_versions_tracks_latest_write_per_subresource() {
+        let writes = vec![
+            ResourceRangeWrite {
+                range: None,
+                version: 1,
+            },
+            ResourceRangeWrite {
+                range: mip(1),
+                version: 2,
+            },
+            ResourceRangeWrite {
+                range: mip(2),
+                version: 3,
+            },
+        ];
+
+        assert_eq!(resolve_read_versions(&writes, mip(1).as_ref(), 3), vec![2]);
+        assert_eq!(resolve_read_versions(&writes, mip(0).as_ref(), 3), vec![1]);
+        assert_eq!(resolve_read_versions(&writes, None, 3), vec![3, 2, 1]);
+        assert_eq!(resolve_read_versions(&writes, mip(2).as_ref(), 2), vec![1]);
+        assert_eq!(resolve_read_versions(&[], mip(0).as_ref(), 0), vec![0]);
+    }
+}
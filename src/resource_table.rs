@@ -1,51 +1,132 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
-use wgpu::Device;
+use wgpu::{CommandBuffer, Device};
 
 use crate::{
-    AnyTransientResource, ArcAnyTransientResource, IndexHandle, ResourceNode, ResourceRef,
-    ResourceRelease, ResourceRequese, ResourceView, TransientResource, TransientResourceCache,
-    TransientResourceCreator, VirtualResource,
+    AnyTransientResource, ArcAnyTransientResource, DebugFill, IndexHandle,
+    IntoArcAnyTransientResource, ResourceNode, ResourceRef, ResourceRelease, ResourceRequese,
+    ResourceView, TransientResource, TransientResourceCache, TransientResourceCreator,
+    VirtualResource,
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceAcquisition {
+    Imported,
+    History,
+    Cached,
+    Created,
+}
+
 #[derive(Default)]
 pub struct ResourceTable {
     resources: HashMap<IndexHandle<ResourceNode>, AnyTransientResource>,
+    exported_resources: HashMap<IndexHandle<ResourceNode>, ArcAnyTransientResource>,
+    debug_fill: Option<DebugFill>,
+    accesses: Mutex<Option<Vec<IndexHandle<ResourceNode>>>>,
 }
 
 impl ResourceTable {
+    pub fn set_debug_fill(&mut self, device: Option<&Device>) {
+        self.debug_fill = device.map(DebugFill::new);
+    }
+
+    pub fn debug_fill(&self) -> Option<&DebugFill> {
+        self.debug_fill.as_ref()
+    }
+
+    pub(crate) fn finish_debug_fill(&mut self) -> Option<CommandBuffer> {
+        self.debug_fill.as_mut().and_then(DebugFill::finish)
+    }
+
+    pub(crate) fn begin_access_tracking(&self) {
+        *self.accesses.lock().unwrap() = Some(vec![]);
+    }
+
+    pub(crate) fn end_access_tracking(&self) -> Vec<IndexHandle<ResourceNode>> {
+        self.accesses.lock().unwrap().take().unwrap_or_default()
+    }
+
+    fn record_access(&self, index: IndexHandle<ResourceNode>) {
+        if let Some(accesses) = self.accesses.lock().unwrap().as_mut()
+            && !accesses.contains(&index)
+        {
+            accesses.push(index);
+        }
+    }
+
     pub fn get_resource<ResourceType: TransientResource, ViewType: ResourceView>(
         &self,
         resource_ref: &ResourceRef<ResourceType, ViewType>,
     ) -> &ResourceType {
+        self.record_access(resource_ref.raw.index);
+
         self.resources
             .get(&resource_ref.raw.index)
             .map(|res| TransientResource::borrow_resource(res))
             .expect("must have resource")
     }
 
+    pub(crate) fn get_any_resource(
+        &self,
+        index: &IndexHandle<ResourceNode>,
+    ) -> Option<&AnyTransientResource> {
+        self.resources.get(index)
+    }
+
     pub fn request_resource(
         &mut self,
         request: &ResourceRequese,
-        device: &Device,
+        resource_creator: &dyn TransientResourceCreator,
         transient_resource_cache: &mut TransientResourceCache,
-    ) {
+    ) -> ResourceAcquisition {
         let index = request.index;
-        let resource = match &request.resource {
-            VirtualResource::Imported(resource) => match &resource {
-                ArcAnyTransientResource::Texture(resource) => {
-                    AnyTransientResource::ImportedTexture(resource.clone())
-                }
-                ArcAnyTransientResource::Buffer(resource) => {
-                    AnyTransientResource::ImportedBuffer(resource.clone())
-                }
+        let (resource, acquisition) = match &request.resource {
+            VirtualResource::Imported(resource) => {
+                (resource.clone().into(), ResourceAcquisition::Imported)
+            }
+            VirtualResource::Setuped(desc) => match transient_resource_cache.get_resource(desc) {
+                Some(resource) => (resource, ResourceAcquisition::Cached),
+                None => (
+                    resource_creator.create_resource(desc),
+                    ResourceAcquisition::Created,
+                ),
             },
-            VirtualResource::Setuped(desc) => transient_resource_cache
-                .get_resource(desc)
-                .unwrap_or_else(|| device.create_resource(desc)),
+            VirtualResource::History(desc) => (
+                transient_resource_cache
+                    .get_history_resource(desc, resource_creator)
+                    .into(),
+                ResourceAcquisition::History,
+            ),
         };
 
+        #[cfg(feature = "tracing")]
+        match acquisition {
+            ResourceAcquisition::Created => {
+                tracing::debug!(resource = %request.name, "created transient resource")
+            }
+            ResourceAcquisition::Cached => {
+                tracing::debug!(resource = %request.name, "reused cached transient resource")
+            }
+            ResourceAcquisition::History => {
+                tracing::debug!(resource = %request.name, "acquired history resource")
+            }
+            ResourceAcquisition::Imported => {
+                tracing::trace!(resource = %request.name, "bound imported resource")
+            }
+        }
+
+        if let (Some(debug_fill), VirtualResource::Setuped(_)) =
+            (&mut self.debug_fill, &request.resource)
+        {
+            debug_fill.fill(&request.name, &resource);
+        }
+
         self.resources.insert(index, resource);
+
+        acquisition
     }
 
     pub fn release_resource(
@@ -54,6 +135,12 @@ impl ResourceTable {
         transient_resource_cache: &mut TransientResourceCache,
     ) {
         if let Some(resource) = self.resources.remove(&release.index) {
+            if release.exported {
+                self.exported_resources
+                    .insert(release.index, resource.into_arc_transient_resource());
+                return;
+            }
+
             match resource {
                 AnyTransientResource::OwnedBuffer(buffer) => {
                     transient_resource_cache.insert_resource(
@@ -71,4 +158,13 @@ impl ResourceTable {
             }
         }
     }
+
+    pub fn take_exported_resource<ResourceType: IntoArcAnyTransientResource>(
+        &mut self,
+        index: &IndexHandle<ResourceNode>,
+    ) -> Option<Arc<ResourceType>> {
+        self.exported_resources
+            .remove(index)
+            .and_then(IntoArcAnyTransientResource::from_arc_transient_resource)
+    }
 }
@@ -1,14 +1,16 @@
 use wgpu::{TextureAspect, TextureFormat, TextureUsages, TextureView, TextureViewDimension};
 
 use crate::{
-    ResourceRead, ResourceRef, ResourceView, ResourceWrite, TransientTexture, pass::PassContext,
+    ImportedTextureView, ResourceRead, ResourceRef, ResourceView, ResourceWrite, TransientTexture,
+    pass::PassContext,
 };
 
 pub type TransientTextureViewRead = TransientTextureView<ResourceRead>;
 
 pub type TransientTextureViewWrite = TransientTextureView<ResourceWrite>;
 
-#[derive(Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransientTextureViewDescriptor {
     pub label: Option<String>,
     pub format: Option<TextureFormat>,
@@ -43,23 +45,30 @@ pub struct TransientTextureView<ViewType> {
 }
 
 impl<ViewType: ResourceView> TransientTextureView<ViewType> {
-    pub fn create_texture_view(&self, context: &PassContext) -> TextureView {
-        let resource = context.get_resource(&self.texture);
-        resource.resource.create_view(&self.desc.get_desc())
+    pub fn create_texture_view(&self, context: &mut PassContext) -> TextureView {
+        context.get_or_create_texture_view(&self.texture, &self.desc)
     }
 }
 
 pub enum TextureViewEdge {
     Read(TransientTextureViewRead),
     Write(TransientTextureViewWrite),
+    ImportedRead(ResourceRef<ImportedTextureView, ResourceRead>),
+    ImportedWrite(ResourceRef<ImportedTextureView, ResourceWrite>),
     Owned(TextureView),
 }
 
 impl TextureViewEdge {
-    pub fn create_texture_view(&self, context: &PassContext) -> TextureView {
+    pub fn create_texture_view(&self, context: &mut PassContext) -> TextureView {
         match self {
             TextureViewEdge::Read(desc) => desc.create_texture_view(context),
             TextureViewEdge::Write(desc) => desc.create_texture_view(context),
+            TextureViewEdge::ImportedRead(texture_view) => {
+                context.get_resource(texture_view).resource.clone()
+            }
+            TextureViewEdge::ImportedWrite(texture_view) => {
+                context.get_resource(texture_view).resource.clone()
+            }
             TextureViewEdge::Owned(texture_view) => texture_view.clone(),
         }
     }
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use wgpu::{BindGroup, Buffer, Texture};
+
+use crate::{BindGroupKey, TransientResourceCreator};
+
+use super::TextureViewCache;
+
+struct CachedBindGroup {
+    bind_group: BindGroup,
+    idle: bool,
+}
+
+#[derive(Default)]
+pub struct BindGroupCache {
+    bind_groups: HashMap<BindGroupKey, CachedBindGroup>,
+    created_count: u64,
+}
+
+impl BindGroupCache {
+    pub fn get_or_create_bind_group(
+        &mut self,
+        key: BindGroupKey,
+        label: Option<String>,
+        resource_creator: &dyn TransientResourceCreator,
+        texture_view_cache: &mut TextureViewCache,
+    ) -> BindGroup {
+        let created_count = &mut self.created_count;
+        let cached = self.bind_groups.entry(key).or_insert_with_key(|key| {
+            *created_count += 1;
+            let desc = key.create_bind_group_descriptor(label, texture_view_cache);
+
+            CachedBindGroup {
+                bind_group: resource_creator.create_bind_group(&desc),
+                idle: false,
+            }
+        });
+        cached.idle = false;
+
+        cached.bind_group.clone()
+    }
+
+    pub fn invalidate_buffer(&mut self, buffer: &Buffer) {
+        self.bind_groups
+            .retain(|key, _| !key.references_buffer(buffer));
+    }
+
+    pub fn invalidate_texture(&mut self, texture: &Texture) {
+        self.bind_groups
+            .retain(|key, _| !key.references_texture(texture));
+    }
+
+    pub fn end_frame(&mut self) {
+        self.bind_groups.retain(|_, cached| !cached.idle);
+
+        for cached in self.bind_groups.values_mut() {
+            cached.idle = true;
+        }
+    }
+
+    pub fn created_count(&self) -> u64 {
+        self.created_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.bind_groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bind_groups.is_empty()
+    }
+}
@@ -7,13 +7,21 @@ use wgpu::{
 
 use super::{
     AnyTransientResource, AnyTransientResourceDescriptor, ArcAnyTransientResource,
-    IntoArcAnyTransientResource, TransientResource, TransientResourceDescriptor,
+    CreatableResourceDescriptor, IntoArcAnyTransientResource, TransientResource,
+    TransientResourceDescriptor,
 };
 
 impl IntoArcAnyTransientResource for TransientBuffer {
     fn into_arc_transient_resource(self: Arc<Self>) -> ArcAnyTransientResource {
         ArcAnyTransientResource::Buffer(self)
     }
+
+    fn from_arc_transient_resource(resource: ArcAnyTransientResource) -> Option<Arc<Self>> {
+        match resource {
+            ArcAnyTransientResource::Buffer(resource) => Some(resource),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -41,6 +49,7 @@ impl TransientResource for TransientBuffer {
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransientBufferDescriptor {
     pub label: Option<String>,
     pub size: BufferAddress,
@@ -85,6 +94,15 @@ impl TransientBufferDescriptor {
         }
     }
 
+    pub fn from_buffer(buffer: &Buffer) -> Self {
+        Self {
+            label: None,
+            size: buffer.size(),
+            usage: buffer.usage(),
+            mapped_at_creation: false,
+        }
+    }
+
     pub fn get_desc(&self) -> BufferDescriptor<'_> {
         BufferDescriptor {
             label: self.label.as_deref(),
@@ -101,6 +119,8 @@ impl From<TransientBufferDescriptor> for AnyTransientResourceDescriptor {
     }
 }
 
+impl CreatableResourceDescriptor for TransientBufferDescriptor {}
+
 impl TransientResourceDescriptor for TransientBufferDescriptor {
     type Resource = TransientBuffer;
 
@@ -1,33 +1,728 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
-use super::{AnyTransientResource, AnyTransientResourceDescriptor};
+use wgpu::{BindGroup, Buffer, Queue, RenderBundle, Texture, TextureView};
+
+use crate::{
+    BindGroupKey, RenderBundleCommand, TransientRenderBundleDescriptor, TransientResourceCreator,
+    TransientTextureViewDescriptor,
+};
+
+use super::{
+    AnyTransientResource, AnyTransientResourceDescriptor, ArcAnyTransientResource, BindGroupCache,
+    HistoryResourceCache, HistoryResourceDescriptor, RenderBundleCache, TextureViewCache,
+};
+
+struct CachedTransientResource {
+    resource: AnyTransientResource,
+    released_frame: u64,
+}
+
+pub const DEFAULT_FRAMES_IN_FLIGHT: u32 = 2;
 
-#[derive(Default)]
 pub struct TransientResourceCache {
-    resources: HashMap<AnyTransientResourceDescriptor, Vec<AnyTransientResource>>,
+    resources: HashMap<AnyTransientResourceDescriptor, Vec<CachedTransientResource>>,
+    bind_group_cache: BindGroupCache,
+    texture_view_cache: TextureViewCache,
+    render_bundle_cache: RenderBundleCache,
+    history_resource_cache: HistoryResourceCache,
+    frame_index: u64,
+    frames_in_flight: u32,
+    completed_frames: Arc<AtomicU64>,
+    deferred_submission: Option<(Queue, u64)>,
+}
+
+impl Default for TransientResourceCache {
+    fn default() -> Self {
+        Self {
+            resources: Default::default(),
+            bind_group_cache: Default::default(),
+            texture_view_cache: Default::default(),
+            render_bundle_cache: Default::default(),
+            history_resource_cache: Default::default(),
+            frame_index: 0,
+            frames_in_flight: DEFAULT_FRAMES_IN_FLIGHT,
+            completed_frames: Default::default(),
+            deferred_submission: None,
+        }
+    }
 }
 
 impl TransientResourceCache {
+    pub fn frame_index(&self) -> u64 {
+        self.frame_index
+    }
+
+    pub fn frames_in_flight(&self) -> u32 {
+        self.frames_in_flight
+    }
+
+    pub fn set_frames_in_flight(&mut self, frames_in_flight: u32) {
+        self.frames_in_flight = frames_in_flight.max(1);
+    }
+
+    pub fn track_submission(&self, queue: &Queue) {
+        let completed_frames = self.completed_frames.clone();
+        let frame_count = self.frame_index;
+
+        queue.on_submitted_work_done(move || {
+            completed_frames.fetch_max(frame_count, Ordering::AcqRel);
+        });
+    }
+
+    pub fn defer_submission_tracking(&mut self, queue: &Queue) {
+        self.deferred_submission = Some((queue.clone(), self.frame_index));
+    }
+
+    pub fn track_deferred_submission(&mut self) {
+        let Some((queue, frame_count)) = self.deferred_submission.take() else {
+            return;
+        };
+
+        let completed_frames = self.completed_frames.clone();
+        queue.on_submitted_work_done(move || {
+            completed_frames.fetch_max(frame_count, Ordering::AcqRel);
+        });
+    }
+
+    pub fn is_frame_completed(&self, frame_index: u64) -> bool {
+        frame_index < self.completed_frames.load(Ordering::Acquire)
+    }
+
     pub fn get_resource(
         &mut self,
         desc: &AnyTransientResourceDescriptor,
     ) -> Option<AnyTransientResource> {
+        if desc.is_cpu_writable() {
+            return self.get_cpu_writable_resource(desc);
+        }
+
         if let Some(entry) = self.resources.get_mut(desc) {
-            entry.pop()
+            entry.pop().map(|cached| cached.resource)
         } else {
             None
         }
     }
 
+    pub fn get_cpu_writable_resource(
+        &mut self,
+        desc: &AnyTransientResourceDescriptor,
+    ) -> Option<AnyTransientResource> {
+        self.track_deferred_submission();
+
+        let completed_frames = self.completed_frames.load(Ordering::Acquire);
+        let entry = self.resources.get_mut(desc)?;
+
+        let position = entry
+            .iter()
+            .position(|cached| cached.released_frame < completed_frames)?;
+
+        Some(entry.swap_remove(position).resource)
+    }
+
     pub fn insert_resource(
         &mut self,
         desc: AnyTransientResourceDescriptor,
         resource: AnyTransientResource,
     ) {
+        let cached = CachedTransientResource {
+            resource,
+            released_frame: self.frame_index,
+        };
+
         if let Some(entry) = self.resources.get_mut(&desc) {
-            entry.push(resource);
+            entry.push(cached);
         } else {
-            self.resources.insert(desc, vec![resource]);
+            self.resources.insert(desc, vec![cached]);
         }
     }
+
+    pub fn get_history_resource(
+        &mut self,
+        desc: &HistoryResourceDescriptor,
+        resource_creator: &dyn TransientResourceCreator,
+    ) -> ArcAnyTransientResource {
+        self.history_resource_cache
+            .get_resource(desc, resource_creator)
+    }
+
+    pub fn get_or_create_bind_group(
+        &mut self,
+        key: BindGroupKey,
+        label: Option<String>,
+        resource_creator: &dyn TransientResourceCreator,
+    ) -> BindGroup {
+        self.bind_group_cache.get_or_create_bind_group(
+            key,
+            label,
+            resource_creator,
+            &mut self.texture_view_cache,
+        )
+    }
+
+    pub fn get_or_create_texture_view(
+        &mut self,
+        texture: &Texture,
+        desc: &TransientTextureViewDescriptor,
+    ) -> TextureView {
+        self.texture_view_cache
+            .get_or_create_texture_view(texture, desc)
+    }
+
+    pub fn get_or_create_render_bundle(
+        &mut self,
+        desc: &TransientRenderBundleDescriptor,
+        commands: Vec<RenderBundleCommand>,
+        resource_creator: &dyn TransientResourceCreator,
+    ) -> RenderBundle {
+        self.render_bundle_cache.get_or_create_render_bundle(
+            desc,
+            commands,
+            resource_creator,
+            &mut self.bind_group_cache,
+            &mut self.texture_view_cache,
+        )
+    }
+
+    pub fn bind_group_cache(&self) -> &BindGroupCache {
+        &self.bind_group_cache
+    }
+
+    pub fn texture_view_cache(&self) -> &TextureViewCache {
+        &self.texture_view_cache
+    }
+
+    pub fn render_bundle_cache(&self) -> &RenderBundleCache {
+        &self.render_bundle_cache
+    }
+
+    pub fn history_resource_cache(&self) -> &HistoryResourceCache {
+        &self.history_resource_cache
+    }
+
+    pub fn history_resource_cache_mut(&mut self) -> &mut HistoryResourceCache {
+        &mut self.history_resource_cache
+    }
+
+    fn owned_textures(&self) -> Vec<Texture> {
+        let pooled =
+            self.resources
+                .values()
+                .flatten()
+                .filter_map(|cached| match &cached.resource {
+                    AnyTransientResource::OwnedTexture(texture) => Some(&texture.resource),
+                    _ => None,
+                });
+        let history =
+            self.history_resource_cache
+                .resources()
+                .filter_map(|resource| match resource {
+                    ArcAnyTransientResource::Texture(texture) => Some(&texture.resource),
+                    _ => None,
+                });
+
+        pooled.chain(history).cloned().collect()
+    }
+
+    fn invalidate_buffer(&mut self, buffer: &Buffer) {
+        self.bind_group_cache.invalidate_buffer(buffer);
+        self.render_bundle_cache.invalidate_buffer(buffer);
+    }
+
+    fn invalidate_texture(&mut self, texture: &Texture) {
+        self.bind_group_cache.invalidate_texture(texture);
+        self.texture_view_cache.invalidate_texture(texture);
+        self.render_bundle_cache.invalidate_texture(texture);
+    }
+
+    fn invalidate_resource(&mut self, resource: &AnyTransientResource) {
+        match resource {
+            AnyTransientResource::OwnedBuffer(buffer) => self.invalidate_buffer(&buffer.resource),
+            AnyTransientResource::ImportedBuffer(buffer) => {
+                self.invalidate_buffer(&buffer.resource)
+            }
+            AnyTransientResource::OwnedTexture(texture) => {
+                self.invalidate_texture(&texture.resource)
+            }
+            AnyTransientResource::ImportedTexture(texture) => {
+                self.invalidate_texture(&texture.resource)
+            }
+            AnyTransientResource::ImportedTextureView(_) => {}
+        }
+    }
+
+    fn evict_stale_cpu_writable_resources(&mut self) -> Vec<AnyTransientResource> {
+        let frame_index = self.frame_index;
+        let frames_in_flight = self.frames_in_flight as u64;
+        let mut evicted = vec![];
+
+        for (desc, entry) in self.resources.iter_mut() {
+            if !desc.is_cpu_writable() {
+                continue;
+            }
+
+            let mut index = 0;
+            while index < entry.len() {
+                if frame_index - entry[index].released_frame >= frames_in_flight {
+                    evicted.push(entry.swap_remove(index).resource);
+                } else {
+                    index += 1;
+                }
+            }
+        }
+        self.resources.retain(|_, entry| !entry.is_empty());
+
+        evicted
+    }
+
+    pub fn end_frame(&mut self) {
+        for resource in self.history_resource_cache.end_frame() {
+            self.invalidate_resource(&AnyTransientResource::from(resource));
+        }
+
+        for resource in self.evict_stale_cpu_writable_resources() {
+            self.invalidate_resource(&resource);
+        }
+
+        self.bind_group_cache.end_frame();
+        let owned_textures = self.owned_textures();
+        self.texture_view_cache
+            .end_frame(|texture| owned_textures.contains(texture));
+        self.render_bundle_cache.end_frame();
+
+        self.frame_index += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.resources.clear();
+        self.bind_group_cache = BindGroupCache::default();
+        self.texture_view_cache = TextureViewCache::default();
+        self.render_bundle_cache = RenderBundleCache::default();
+        self.history_resource_cache = HistoryResourceCache::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu::{
+        BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, Buffer,
+        BufferBindingType, BufferUsages, Device, Extent3d, PollType, ShaderStages, Texture,
+        TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDimension,
+    };
+
+    use crate::{
+        AnyTransientResource, AnyTransientResourceDescriptor, ArcAnyTransientResource,
+        BindGroupEntryKey, BindGroupKey, BindGroupResourceKey, BufferBinding,
+        HistoryResourceDescriptor, HistorySlot, RenderBundleCommand, TextureViewBinding,
+        TransientBufferDescriptor, TransientRenderBundleDescriptor, TransientResourceCache,
+        TransientResourceCreator, TransientTextureDescriptor, TransientTextureViewDescriptor,
+    };
+
+    fn bind_group_layout(device: &Device, ty: BindingType) -> BindGroupLayout {
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::COMPUTE,
+                ty,
+                count: None,
+            }],
+        })
+    }
+
+    fn buffer_key(device: &Device, buffer: &Buffer) -> BindGroupKey {
+        BindGroupKey {
+            layout: bind_group_layout(
+                device,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+            ),
+            entries: vec![BindGroupEntryKey {
+                binding: 0,
+                resource: BindGroupResourceKey::Buffer(BufferBinding {
+                    buffer: buffer.clone(),
+                    offset: 0,
+                    size: None,
+                }),
+            }],
+        }
+    }
+
+    fn texture_key(device: &Device, texture: &Texture) -> BindGroupKey {
+        BindGroupKey {
+            layout: bind_group_layout(
+                device,
+                BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+            ),
+            entries: vec![BindGroupEntryKey {
+                binding: 0,
+                resource: BindGroupResourceKey::TextureView(TextureViewBinding {
+                    texture: texture.clone(),
+                    desc: Default::default(),
+                }),
+            }],
+        }
+    }
+
+    fn history_desc(
+        key: &str,
+        desc: impl Into<AnyTransientResourceDescriptor>,
+    ) -> HistoryResourceDescriptor {
+        HistoryResourceDescriptor {
+            key: key.to_string(),
+            desc: desc.into(),
+            slot: HistorySlot::Current,
+        }
+    }
+
+    #[test]
+    fn transient_resource_cache_reuses_bind_groups_across_frames() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let key = buffer_key(&device, &buffer);
+
+        let first = cache.get_or_create_bind_group(key.clone(), None, &device);
+        cache.end_frame();
+        let second = cache.get_or_create_bind_group(key, None, &device);
+
+        assert_eq!(first, second);
+        assert_eq!(cache.bind_group_cache().created_count(), 1);
+        assert_eq!(cache.bind_group_cache().len(), 1);
+    }
+
+    #[test]
+    fn transient_resource_cache_invalidates_bind_groups_of_evicted_resources() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let buffer_desc = history_desc(
+            "buffer",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+        );
+        let texture_desc = history_desc(
+            "texture",
+            TransientTextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: vec![],
+            },
+        );
+
+        let ArcAnyTransientResource::Buffer(buffer) =
+            cache.get_history_resource(&buffer_desc, &device)
+        else {
+            panic!("expected a buffer");
+        };
+        let ArcAnyTransientResource::Texture(texture) =
+            cache.get_history_resource(&texture_desc, &device)
+        else {
+            panic!("expected a texture");
+        };
+        let buffer_key = buffer_key(&device, &buffer.resource);
+        let texture_key = texture_key(&device, &texture.resource);
+
+        for _ in 0..2 {
+            cache.get_or_create_bind_group(buffer_key.clone(), None, &device);
+            cache.get_or_create_bind_group(texture_key.clone(), None, &device);
+            assert_eq!(cache.bind_group_cache().len(), 2);
+            assert_eq!(cache.texture_view_cache().len(), 1);
+
+            cache.end_frame();
+        }
+
+        cache.get_or_create_bind_group(buffer_key, None, &device);
+        cache.get_or_create_bind_group(texture_key, None, &device);
+        cache.end_frame();
+
+        assert!(!cache.history_resource_cache().contains("buffer"));
+        assert!(!cache.history_resource_cache().contains("texture"));
+        assert!(cache.bind_group_cache().is_empty());
+        assert!(cache.texture_view_cache().is_empty());
+        assert_eq!(cache.bind_group_cache().created_count(), 2);
+    }
+
+    #[test]
+    fn transient_resource_cache_invalidates_bind_groups_of_replaced_history_resources() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let desc = |width| {
+            history_desc(
+                "texture",
+                TransientTextureDescriptor {
+                    size: Extent3d {
+                        width,
+                        height: 4,
+                        depth_or_array_layers: 1,
+                    },
+                    ..texture_desc()
+                },
+            )
+        };
+
+        let ArcAnyTransientResource::Texture(texture) =
+            cache.get_history_resource(&desc(4), &device)
+        else {
+            panic!("expected a texture");
+        };
+        cache.get_or_create_bind_group(texture_key(&device, &texture.resource), None, &device);
+        assert_eq!(cache.bind_group_cache().len(), 1);
+
+        cache.get_history_resource(&desc(8), &device);
+        cache.end_frame();
+
+        assert!(cache.history_resource_cache().contains("texture"));
+        assert!(cache.bind_group_cache().is_empty());
+        assert!(cache.texture_view_cache().is_empty());
+    }
+
+    fn texture_desc() -> TransientTextureDescriptor {
+        TransientTextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: vec![],
+        }
+    }
+
+    #[test]
+    fn transient_resource_cache_keeps_texture_views_while_texture_is_pooled() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let desc = AnyTransientResourceDescriptor::from(texture_desc());
+        let AnyTransientResource::OwnedTexture(texture) = device.create_resource(&desc) else {
+            panic!("expected a texture");
+        };
+        let view_desc = TransientTextureViewDescriptor::default();
+
+        let view = cache.get_or_create_texture_view(&texture.resource, &view_desc);
+        let imported = device.create_texture(&texture_desc().get_desc());
+        cache.get_or_create_texture_view(&imported, &view_desc);
+
+        let pooled = texture.resource.clone();
+        cache.insert_resource(desc, texture.into());
+
+        for _ in 0..3 {
+            cache.end_frame();
+        }
+
+        assert_eq!(cache.texture_view_cache().len(), 1);
+        assert_eq!(cache.get_or_create_texture_view(&pooled, &view_desc), view);
+        assert_eq!(cache.texture_view_cache().created_count(), 2);
+    }
+
+    fn render_bundle_desc(key: &str) -> TransientRenderBundleDescriptor {
+        TransientRenderBundleDescriptor {
+            key: key.to_string(),
+            color_formats: vec![Some(TextureFormat::Rgba8Unorm)],
+            depth_stencil: None,
+            sample_count: 1,
+            multiview: None,
+        }
+    }
+
+    fn set_vertex_buffer(buffer: &Buffer, size: u64) -> RenderBundleCommand {
+        RenderBundleCommand::SetVertexBuffer {
+            slot: 0,
+            buffer: buffer.clone(),
+            offset: 0,
+            size,
+        }
+    }
+
+    #[test]
+    fn transient_resource_cache_reuses_render_bundles_until_commands_change() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::VERTEX,
+            mapped_at_creation: false,
+        });
+        let desc = render_bundle_desc("bundle");
+
+        let first =
+            cache.get_or_create_render_bundle(&desc, vec![set_vertex_buffer(&buffer, 64)], &device);
+        cache.end_frame();
+        let second =
+            cache.get_or_create_render_bundle(&desc, vec![set_vertex_buffer(&buffer, 64)], &device);
+        assert_eq!(first, second);
+
+        let changed =
+            cache.get_or_create_render_bundle(&desc, vec![set_vertex_buffer(&buffer, 32)], &device);
+        assert_ne!(first, changed);
+        assert_eq!(cache.render_bundle_cache().len(), 1);
+    }
+
+    #[test]
+    fn transient_resource_cache_invalidates_render_bundles_of_evicted_resources() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let buffer_desc = history_desc(
+            "buffer",
+            TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::VERTEX,
+                mapped_at_creation: false,
+            },
+        );
+        let texture_desc = history_desc("texture", texture_desc());
+
+        let ArcAnyTransientResource::Buffer(buffer) =
+            cache.get_history_resource(&buffer_desc, &device)
+        else {
+            panic!("expected a buffer");
+        };
+        let ArcAnyTransientResource::Texture(texture) =
+            cache.get_history_resource(&texture_desc, &device)
+        else {
+            panic!("expected a texture");
+        };
+        let buffer_commands = vec![set_vertex_buffer(&buffer.resource, 64)];
+        let texture_commands = vec![RenderBundleCommand::SetBindGroup {
+            index: 0,
+            key: texture_key(&device, &texture.resource),
+            label: None,
+            offsets: vec![],
+        }];
+
+        for _ in 0..3 {
+            cache.get_or_create_render_bundle(
+                &render_bundle_desc("buffer"),
+                buffer_commands.clone(),
+                &device,
+            );
+            cache.get_or_create_render_bundle(
+                &render_bundle_desc("texture"),
+                texture_commands.clone(),
+                &device,
+            );
+            assert_eq!(cache.render_bundle_cache().len(), 2);
+
+            cache.end_frame();
+        }
+
+        assert!(cache.render_bundle_cache().is_empty());
+    }
+
+    #[test]
+    fn transient_resource_cache_reuses_cpu_writable_resources_after_gpu_completion() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let desc = AnyTransientResourceDescriptor::from(TransientBufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let resource = device.create_resource(&desc);
+        cache.insert_resource(desc.clone(), resource);
+        assert!(cache.get_resource(&desc).is_none());
+
+        cache.end_frame();
+        assert!(!cache.is_frame_completed(0));
+        assert!(cache.get_resource(&desc).is_none());
+
+        queue.submit([]);
+        cache.track_submission(&queue);
+        device.poll(PollType::wait_indefinitely()).unwrap();
+
+        assert!(cache.is_frame_completed(0));
+        assert!(!cache.is_frame_completed(1));
+        assert!(cache.get_resource(&desc).is_some());
+    }
+
+    #[test]
+    fn transient_resource_cache_evicts_cpu_writable_resources_after_frames_in_flight() {
+        let (device, queue) = wgpu::Device::noop(&Default::default());
+
+        let desc = AnyTransientResourceDescriptor::from(TransientBufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let reusable_after_two_frames = |frames_in_flight| {
+            let mut cache = TransientResourceCache::default();
+            cache.set_frames_in_flight(frames_in_flight);
+            cache.insert_resource(desc.clone(), device.create_resource(&desc));
+
+            cache.end_frame();
+            cache.end_frame();
+
+            queue.submit([]);
+            cache.track_submission(&queue);
+            device.poll(PollType::wait_indefinitely()).unwrap();
+
+            cache.get_resource(&desc).is_some()
+        };
+
+        assert!(reusable_after_two_frames(3));
+        assert!(!reusable_after_two_frames(1));
+    }
+
+    #[test]
+    fn transient_resource_cache_reuses_gpu_only_resources_immediately() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = TransientResourceCache::default();
+
+        let desc = AnyTransientResourceDescriptor::from(TransientBufferDescriptor {
+            label: None,
+            size: 64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let resource = device.create_resource(&desc);
+        cache.insert_resource(desc.clone(), resource);
+
+        assert!(cache.get_resource(&desc).is_some());
+    }
 }
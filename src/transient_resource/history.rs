@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use crate::TransientResourceCreator;
+
+use super::{AnyTransientResourceDescriptor, ArcAnyTransientResource};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HistorySlot {
+    Previous,
+    Current,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HistoryResourceDescriptor {
+    pub key: String,
+    pub desc: AnyTransientResourceDescriptor,
+    pub slot: HistorySlot,
+}
+
+struct HistoryEntry {
+    desc: AnyTransientResourceDescriptor,
+    resources: [ArcAnyTransientResource; 2],
+    current: usize,
+    declared: bool,
+    unused_frames: u32,
+}
+
+impl HistoryEntry {
+    fn new(
+        desc: &AnyTransientResourceDescriptor,
+        resource_creator: &dyn TransientResourceCreator,
+    ) -> Self {
+        let create = || {
+            resource_creator
+                .create_resource(desc)
+                .into_arc_transient_resource()
+        };
+
+        HistoryEntry {
+            desc: desc.clone(),
+            resources: [create(), create()],
+            current: 0,
+            declared: false,
+            unused_frames: 0,
+        }
+    }
+}
+
+pub const DEFAULT_HISTORY_MAX_UNUSED_FRAMES: u32 = 1;
+
+pub struct HistoryResourceCache {
+    entries: HashMap<String, HistoryEntry>,
+    replaced: Vec<ArcAnyTransientResource>,
+    max_unused_frames: u32,
+}
+
+impl Default for HistoryResourceCache {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+            replaced: vec![],
+            max_unused_frames: DEFAULT_HISTORY_MAX_UNUSED_FRAMES,
+        }
+    }
+}
+
+impl HistoryResourceCache {
+    pub fn set_max_unused_frames(&mut self, max_unused_frames: u32) {
+        self.max_unused_frames = max_unused_frames;
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn get_resource(
+        &mut self,
+        desc: &HistoryResourceDescriptor,
+        resource_creator: &dyn TransientResourceCreator,
+    ) -> ArcAnyTransientResource {
+        if let Some(entry) = self.entries.get(&desc.key)
+            && entry.desc != desc.desc
+            && let Some(entry) = self.entries.remove(&desc.key)
+        {
+            self.replaced.extend(entry.resources);
+        }
+
+        let entry = self
+            .entries
+            .entry(desc.key.clone())
+            .or_insert_with(|| HistoryEntry::new(&desc.desc, resource_creator));
+        entry.declared = true;
+
+        let index = match desc.slot {
+            HistorySlot::Current => entry.current,
+            HistorySlot::Previous => 1 - entry.current,
+        };
+
+        entry.resources[index].clone()
+    }
+
+    pub fn end_frame(&mut self) -> Vec<ArcAnyTransientResource> {
+        for entry in self.entries.values_mut() {
+            if entry.declared {
+                entry.current = 1 - entry.current;
+                entry.unused_frames = 0;
+            } else {
+                entry.unused_frames += 1;
+            }
+
+            entry.declared = false;
+        }
+
+        let max_unused_frames = self.max_unused_frames;
+        let mut evicted = std::mem::take(&mut self.replaced);
+
+        self.entries.retain(|_, entry| {
+            if entry.unused_frames > max_unused_frames {
+                evicted.extend(entry.resources.iter().cloned());
+                false
+            } else {
+                true
+            }
+        });
+
+        evicted
+    }
+
+    pub fn resources(&self) -> impl Iterator<Item = &ArcAnyTransientResource> {
+        self.entries
+            .values()
+            .flat_map(|entry| entry.resources.iter())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use wgpu::BufferUsages;
+
+    use crate::{
+        ArcAnyTransientResource, HistoryResourceCache, HistoryResourceDescriptor, HistorySlot,
+        TransientBufferDescriptor,
+    };
+
+    fn history_desc(slot: HistorySlot) -> HistoryResourceDescriptor {
+        HistoryResourceDescriptor {
+            key: "history".to_string(),
+            desc: TransientBufferDescriptor {
+                label: None,
+                size: 64,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            }
+            .into(),
+            slot,
+        }
+    }
+
+    fn same_resource(a: &ArcAnyTransientResource, b: &ArcAnyTransientResource) -> bool {
+        match (a, b) {
+            (ArcAnyTransientResource::Buffer(a), ArcAnyTransientResource::Buffer(b)) => {
+                Arc::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn history_resource_cache_swaps_previous_and_current() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = HistoryResourceCache::default();
+
+        let previous = cache.get_resource(&history_desc(HistorySlot::Previous), &device);
+        let current = cache.get_resource(&history_desc(HistorySlot::Current), &device);
+        assert!(!same_resource(&previous, &current));
+
+        assert!(cache.end_frame().is_empty());
+
+        let next_previous = cache.get_resource(&history_desc(HistorySlot::Previous), &device);
+        let next_current = cache.get_resource(&history_desc(HistorySlot::Current), &device);
+        assert!(same_resource(&next_previous, &current));
+        assert!(same_resource(&next_current, &previous));
+    }
+
+    #[test]
+    fn history_resource_cache_evicts_entries_unused_for_max_unused_frames() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = HistoryResourceCache::default();
+        cache.set_max_unused_frames(3);
+
+        cache.get_resource(&history_desc(HistorySlot::Current), &device);
+        assert!(cache.end_frame().is_empty());
+
+        for _ in 0..3 {
+            assert!(cache.end_frame().is_empty());
+            assert!(cache.contains("history"));
+        }
+
+        assert_eq!(cache.end_frame().len(), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn history_resource_cache_keeps_entries_that_are_declared_every_frame() {
+        let (device, _) = wgpu::Device::noop(&Default::default());
+        let mut cache = HistoryResourceCache::default();
+        cache.set_max_unused_frames(0);
+
+        for _ in 0..4 {
+            cache.get_resource(&history_desc(HistorySlot::Previous), &device);
+            assert!(cache.end_frame().is_empty());
+        }
+
+        assert_eq!(cache.len(), 1);
+    }
+}
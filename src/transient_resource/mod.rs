@@ -1,25 +1,42 @@
+mod bind_group_cache;
 mod buffer;
 mod cache;
+mod history;
+mod render_bundle_cache;
 mod texture;
+mod texture_view;
+mod texture_view_cache;
 
+pub use bind_group_cache::*;
 pub use buffer::*;
 pub use cache::*;
+pub use history::*;
+pub use render_bundle_cache::*;
 pub use texture::*;
+pub use texture_view::*;
+pub use texture_view_cache::*;
 
 use std::{fmt::Debug, sync::Arc};
+use wgpu::{BufferUsages, Extent3d};
 
 #[derive(Clone)]
 pub enum VirtualResource {
     Setuped(AnyTransientResourceDescriptor),
     Imported(ArcAnyTransientResource),
+    History(HistoryResourceDescriptor),
 }
 
 impl VirtualResource {
-    pub fn get_desc<ResourceType: TransientResource>(&self) -> ResourceType::Descriptor {
-        let desc = match self {
+    pub fn get_any_desc(&self) -> AnyTransientResourceDescriptor {
+        match self {
             VirtualResource::Imported(resource) => resource.get_desc(),
             VirtualResource::Setuped(desc) => desc.clone(),
-        };
+            VirtualResource::History(history) => history.desc.clone(),
+        }
+    }
+
+    pub fn get_desc<ResourceType: TransientResource>(&self) -> ResourceType::Descriptor {
+        let desc = self.get_any_desc();
 
         <ResourceType::Descriptor as TransientResourceDescriptor>::borrow_resource_descriptor(&desc)
             .clone()
@@ -30,6 +47,7 @@ impl VirtualResource {
 pub enum ArcAnyTransientResource {
     Buffer(Arc<TransientBuffer>),
     Texture(Arc<TransientTexture>),
+    TextureView(Arc<ImportedTextureView>),
 }
 
 impl ArcAnyTransientResource {
@@ -41,12 +59,17 @@ impl ArcAnyTransientResource {
             ArcAnyTransientResource::Texture(res) => {
                 AnyTransientResourceDescriptor::Texture(res.desc.clone())
             }
+            ArcAnyTransientResource::TextureView(res) => {
+                AnyTransientResourceDescriptor::TextureView(res.desc.clone())
+            }
         }
     }
 }
 
 pub trait IntoArcAnyTransientResource: TransientResource {
     fn into_arc_transient_resource(self: Arc<Self>) -> ArcAnyTransientResource;
+
+    fn from_arc_transient_resource(resource: ArcAnyTransientResource) -> Option<Arc<Self>>;
 }
 
 pub enum AnyTransientResource {
@@ -54,6 +77,53 @@ pub enum AnyTransientResource {
     ImportedBuffer(Arc<TransientBuffer>),
     OwnedTexture(TransientTexture),
     ImportedTexture(Arc<TransientTexture>),
+    ImportedTextureView(Arc<ImportedTextureView>),
+}
+
+impl AnyTransientResource {
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            AnyTransientResource::OwnedBuffer(buffer) => buffer.desc.label.as_deref(),
+            AnyTransientResource::ImportedBuffer(buffer) => buffer.desc.label.as_deref(),
+            AnyTransientResource::OwnedTexture(texture) => texture.desc.label.as_deref(),
+            AnyTransientResource::ImportedTexture(texture) => texture.desc.label.as_deref(),
+            AnyTransientResource::ImportedTextureView(texture_view) => {
+                texture_view.desc.label.as_deref()
+            }
+        }
+    }
+
+    pub fn into_arc_transient_resource(self) -> ArcAnyTransientResource {
+        match self {
+            AnyTransientResource::OwnedBuffer(buffer) => {
+                ArcAnyTransientResource::Buffer(Arc::new(buffer))
+            }
+            AnyTransientResource::ImportedBuffer(buffer) => ArcAnyTransientResource::Buffer(buffer),
+            AnyTransientResource::OwnedTexture(texture) => {
+                ArcAnyTransientResource::Texture(Arc::new(texture))
+            }
+            AnyTransientResource::ImportedTexture(texture) => {
+                ArcAnyTransientResource::Texture(texture)
+            }
+            AnyTransientResource::ImportedTextureView(texture_view) => {
+                ArcAnyTransientResource::TextureView(texture_view)
+            }
+        }
+    }
+}
+
+impl From<ArcAnyTransientResource> for AnyTransientResource {
+    fn from(value: ArcAnyTransientResource) -> Self {
+        match value {
+            ArcAnyTransientResource::Buffer(buffer) => AnyTransientResource::ImportedBuffer(buffer),
+            ArcAnyTransientResource::Texture(texture) => {
+                AnyTransientResource::ImportedTexture(texture)
+            }
+            ArcAnyTransientResource::TextureView(texture_view) => {
+                AnyTransientResource::ImportedTextureView(texture_view)
+            }
+        }
+    }
 }
 
 impl From<TransientBuffer> for AnyTransientResource {
@@ -80,10 +150,39 @@ impl From<Arc<TransientTexture>> for AnyTransientResource {
     }
 }
 
-#[derive(Clone, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyTransientResourceDescriptor {
     Buffer(TransientBufferDescriptor),
     Texture(TransientTextureDescriptor),
+    TextureView(ImportedTextureViewDescriptor),
+}
+
+impl AnyTransientResourceDescriptor {
+    pub fn is_cpu_writable(&self) -> bool {
+        match self {
+            AnyTransientResourceDescriptor::Buffer(desc) => {
+                desc.mapped_at_creation || desc.usage.contains(BufferUsages::MAP_WRITE)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn texture_size(&self) -> Option<Extent3d> {
+        match self {
+            AnyTransientResourceDescriptor::Buffer(_) => None,
+            AnyTransientResourceDescriptor::Texture(desc) => Some(desc.size),
+            AnyTransientResourceDescriptor::TextureView(desc) => Some(desc.size),
+        }
+    }
+
+    pub fn size_in_bytes(&self) -> u64 {
+        match self {
+            AnyTransientResourceDescriptor::Buffer(desc) => desc.size,
+            AnyTransientResourceDescriptor::Texture(desc) => desc.size_in_bytes(),
+            AnyTransientResourceDescriptor::TextureView(_) => 0,
+        }
+    }
 }
 
 pub trait TransientResource: 'static {
@@ -102,6 +201,8 @@ pub trait TransientResourceDescriptor:
     fn borrow_resource_descriptor(res: &AnyTransientResourceDescriptor) -> &Self;
 }
 
+pub trait CreatableResourceDescriptor: TransientResourceDescriptor {}
+
 pub trait TypeEquals {
     type Other;
     fn same(value: Self) -> Self::Other;
@@ -129,22 +230,10 @@ mod tests {
 
     #[test]
     fn transient_resource_creator_create_buffer() {
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::VULKAN,
-            ..Default::default()
-        });
-
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::LowPower,
-            ..Default::default()
-        }))
-        .expect("Failed to get adapter");
-
-        let (device, _) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        let (device, _) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
             label: Some("Test Device"),
             ..Default::default()
-        }))
-        .expect("Failed to get device");
+        });
 
         let buffer_desc = TransientBufferDescriptor {
             label: Some("test buffer".to_string()),
@@ -168,22 +257,10 @@ mod tests {
 
     #[test]
     fn transient_resource_creator_create_texture() {
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::VULKAN,
-            ..Default::default()
-        });
-
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::LowPower,
-            ..Default::default()
-        }))
-        .expect("Failed to get adapter");
-
-        let (device, _) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        let (device, _) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
             label: Some("Test Device"),
             ..Default::default()
-        }))
-        .expect("Failed to get device");
+        });
 
         let texture_desc = TransientTextureDescriptor {
             label: Some("test texture".to_string()),
@@ -200,6 +277,10 @@ mod tests {
             view_formats: vec![],
         };
         let any_desc = AnyTransientResourceDescriptor::Texture(texture_desc.clone());
+        assert_eq!(
+            any_desc.size_in_bytes(),
+            4 * (256 * 256 + 128 * 128 + 64 * 64 + 32 * 32)
+        );
 
         let resource = device.create_resource(&any_desc);
 
@@ -216,22 +297,10 @@ mod tests {
 
     #[test]
     fn transient_resource_creator_create_multiple_resources() {
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::VULKAN,
-            ..Default::default()
-        });
-
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::LowPower,
-            ..Default::default()
-        }))
-        .expect("Failed to get adapter");
-
-        let (device, _) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        let (device, _) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
             label: Some("Test Device"),
             ..Default::default()
-        }))
-        .expect("Failed to get device");
+        });
 
         let buffer_desc = TransientBufferDescriptor {
             label: None,
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use wgpu::{Buffer, RenderBundle, Texture};
+
+use crate::{
+    RenderBundleCommand, TransientRenderBundleDescriptor, TransientResourceCreator,
+    create_render_bundle,
+};
+
+use super::{BindGroupCache, TextureViewCache};
+
+struct CachedRenderBundle {
+    desc: TransientRenderBundleDescriptor,
+    commands: Vec<RenderBundleCommand>,
+    render_bundle: RenderBundle,
+    idle: bool,
+}
+
+#[derive(Default)]
+pub struct RenderBundleCache {
+    render_bundles: HashMap<String, CachedRenderBundle>,
+}
+
+impl RenderBundleCache {
+    pub fn get_or_create_render_bundle(
+        &mut self,
+        desc: &TransientRenderBundleDescriptor,
+        commands: Vec<RenderBundleCommand>,
+        resource_creator: &dyn TransientResourceCreator,
+        bind_group_cache: &mut BindGroupCache,
+        texture_view_cache: &mut TextureViewCache,
+    ) -> RenderBundle {
+        if let Some(cached) = self.render_bundles.get_mut(&desc.key)
+            && cached.desc == *desc
+            && cached.commands == commands
+        {
+            cached.idle = false;
+            return cached.render_bundle.clone();
+        }
+
+        let bind_groups = commands
+            .iter()
+            .map(|command| match command {
+                RenderBundleCommand::SetBindGroup { key, label, .. } => {
+                    Some(bind_group_cache.get_or_create_bind_group(
+                        key.clone(),
+                        label.clone(),
+                        resource_creator,
+                        texture_view_cache,
+                    ))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let render_bundle =
+            create_render_bundle(resource_creator.device(), desc, &commands, &bind_groups);
+
+        self.render_bundles.insert(
+            desc.key.clone(),
+            CachedRenderBundle {
+                desc: desc.clone(),
+                commands,
+                render_bundle: render_bundle.clone(),
+                idle: false,
+            },
+        );
+
+        render_bundle
+    }
+
+    pub fn invalidate_buffer(&mut self, buffer: &Buffer) {
+        self.render_bundles.retain(|_, cached| {
+            !cached
+                .commands
+                .iter()
+                .any(|command| command.references_buffer(buffer))
+        });
+    }
+
+    pub fn invalidate_texture(&mut self, texture: &Texture) {
+        self.render_bundles.retain(|_, cached| {
+            !cached
+                .commands
+                .iter()
+                .any(|command| command.references_texture(texture))
+        });
+    }
+
+    pub fn end_frame(&mut self) {
+        self.render_bundles.retain(|_, cached| !cached.idle);
+
+        for cached in self.render_bundles.values_mut() {
+            cached.idle = true;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.render_bundles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.render_bundles.is_empty()
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     AnyTransientResource, AnyTransientResourceDescriptor, ArcAnyTransientResource,
-    IntoArcAnyTransientResource, TransientResource, TransientResourceDescriptor,
+    CreatableResourceDescriptor, IntoArcAnyTransientResource, TransientResource,
+    TransientResourceDescriptor,
 };
 use std::sync::Arc;
 use wgpu::{Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
@@ -9,6 +10,13 @@ impl IntoArcAnyTransientResource for TransientTexture {
     fn into_arc_transient_resource(self: Arc<Self>) -> ArcAnyTransientResource {
         ArcAnyTransientResource::Texture(self)
     }
+
+    fn from_arc_transient_resource(resource: ArcAnyTransientResource) -> Option<Arc<Self>> {
+        match resource {
+            ArcAnyTransientResource::Texture(resource) => Some(resource),
+            _ => None,
+        }
+    }
 }
 
 pub struct TransientTexture {
@@ -35,6 +43,7 @@ impl TransientResource for TransientTexture {
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransientTextureDescriptor {
     pub label: Option<String>,
     pub size: Extent3d,
@@ -60,6 +69,40 @@ impl TransientTextureDescriptor {
         }
     }
 
+    pub fn from_texture(texture: &Texture) -> Self {
+        TransientTextureDescriptor {
+            label: None,
+            size: texture.size(),
+            mip_level_count: texture.mip_level_count(),
+            sample_count: texture.sample_count(),
+            dimension: texture.dimension(),
+            format: texture.format(),
+            usage: texture.usage(),
+            view_formats: vec![],
+        }
+    }
+
+    pub fn size_in_bytes(&self) -> u64 {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let block_size = self
+            .format
+            .block_copy_size(None)
+            .or_else(|| self.format.target_pixel_byte_cost())
+            .unwrap_or(4) as u64;
+
+        let bytes = (0..self.mip_level_count)
+            .map(|level| {
+                let size = self.size.mip_level_size(level, self.dimension);
+                let blocks_wide = size.width.div_ceil(block_width) as u64;
+                let blocks_high = size.height.div_ceil(block_height) as u64;
+
+                blocks_wide * blocks_high * size.depth_or_array_layers as u64 * block_size
+            })
+            .sum::<u64>();
+
+        bytes * self.sample_count as u64
+    }
+
     pub fn get_desc(&self) -> TextureDescriptor<'_> {
         TextureDescriptor {
             label: self.label.as_deref(),
@@ -80,6 +123,8 @@ impl From<TransientTextureDescriptor> for AnyTransientResourceDescriptor {
     }
 }
 
+impl CreatableResourceDescriptor for TransientTextureDescriptor {}
+
 impl TransientResourceDescriptor for TransientTextureDescriptor {
     type Resource = TransientTexture;
 
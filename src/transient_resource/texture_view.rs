@@ -0,0 +1,68 @@
+use super::{
+    AnyTransientResource, AnyTransientResourceDescriptor, ArcAnyTransientResource,
+    IntoArcAnyTransientResource, TransientResource, TransientResourceDescriptor,
+};
+use std::sync::Arc;
+use wgpu::{Extent3d, TextureFormat, TextureView};
+
+impl IntoArcAnyTransientResource for ImportedTextureView {
+    fn into_arc_transient_resource(self: Arc<Self>) -> ArcAnyTransientResource {
+        ArcAnyTransientResource::TextureView(self)
+    }
+
+    fn from_arc_transient_resource(resource: ArcAnyTransientResource) -> Option<Arc<Self>> {
+        match resource {
+            ArcAnyTransientResource::TextureView(resource) => Some(resource),
+            _ => None,
+        }
+    }
+}
+
+pub struct ImportedTextureView {
+    pub resource: TextureView,
+    pub desc: ImportedTextureViewDescriptor,
+}
+
+impl TransientResource for ImportedTextureView {
+    type Descriptor = ImportedTextureViewDescriptor;
+
+    fn borrow_resource(res: &AnyTransientResource) -> &Self {
+        match res {
+            AnyTransientResource::ImportedTextureView(res) => res,
+            _ => {
+                unimplemented!()
+            }
+        }
+    }
+
+    fn get_desc(&self) -> &Self::Descriptor {
+        &self.desc
+    }
+}
+
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportedTextureViewDescriptor {
+    pub label: Option<String>,
+    pub format: TextureFormat,
+    pub size: Extent3d,
+}
+
+impl From<ImportedTextureViewDescriptor> for AnyTransientResourceDescriptor {
+    fn from(value: ImportedTextureViewDescriptor) -> Self {
+        AnyTransientResourceDescriptor::TextureView(value)
+    }
+}
+
+impl TransientResourceDescriptor for ImportedTextureViewDescriptor {
+    type Resource = ImportedTextureView;
+
+    fn borrow_resource_descriptor(res: &AnyTransientResourceDescriptor) -> &Self {
+        match res {
+            AnyTransientResourceDescriptor::TextureView(res) => res,
+            _ => {
+                unimplemented!()
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use wgpu::{Texture, TextureView};
+
+use crate::TransientTextureViewDescriptor;
+
+#[derive(Default)]
+struct CachedTextureViews {
+    views: HashMap<TransientTextureViewDescriptor, TextureView>,
+    idle: bool,
+}
+
+#[derive(Default)]
+pub struct TextureViewCache {
+    textures: HashMap<Texture, CachedTextureViews>,
+    created_count: u64,
+}
+
+impl TextureViewCache {
+    pub fn get_or_create_texture_view(
+        &mut self,
+        texture: &Texture,
+        desc: &TransientTextureViewDescriptor,
+    ) -> TextureView {
+        let cached = self.textures.entry(texture.clone()).or_default();
+        cached.idle = false;
+
+        if let Some(view) = cached.views.get(desc) {
+            return view.clone();
+        }
+
+        let view = texture.create_view(&desc.get_desc());
+        self.created_count += 1;
+        cached.views.insert(desc.clone(), view.clone());
+
+        view
+    }
+
+    pub fn invalidate_texture(&mut self, texture: &Texture) {
+        self.textures.remove(texture);
+    }
+
+    pub fn end_frame(&mut self, is_owned: impl Fn(&Texture) -> bool) {
+        self.textures
+            .retain(|texture, cached| !cached.idle || is_owned(texture));
+
+        for cached in self.textures.values_mut() {
+            cached.idle = true;
+        }
+    }
+
+    pub fn created_count(&self) -> u64 {
+        self.created_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.textures
+            .values()
+            .map(|cached| cached.views.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+}
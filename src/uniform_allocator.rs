@@ -0,0 +1,183 @@
+use std::num::{NonZero, TryFromIntError};
+
+use wgpu::BufferUsages;
+
+use crate::{
+    FrameGraph, IndexHandle, ResourceHandle, ResourceNode, ResourceRead, ResourceRef,
+    TransientBindGroupBuffer, TransientBuffer, TransientBufferDescriptor, VirtualResource,
+};
+
+pub const UNIFORM_BUFFER_ALIGNMENT: u64 = 256;
+
+pub const DEFAULT_UNIFORM_PAGE_SIZE: u64 = 64 * 1024;
+
+fn align_to(value: u64, alignment: u64) -> u64 {
+    value.div_ceil(alignment) * alignment
+}
+
+#[derive(Clone)]
+pub struct UniformAllocation {
+    pub buffer: ResourceRef<TransientBuffer, ResourceRead>,
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl UniformAllocation {
+    pub fn dynamic_offset(&self) -> Result<u32, TryFromIntError> {
+        u32::try_from(self.offset)
+    }
+
+    pub fn get_bind_group_buffer(&self) -> TransientBindGroupBuffer {
+        TransientBindGroupBuffer {
+            buffer: self.buffer.clone(),
+            size: NonZero::new(self.size),
+            offset: 0,
+        }
+    }
+}
+
+pub struct UniformAllocator {
+    pages: Vec<IndexHandle<ResourceNode>>,
+    page_size: u64,
+}
+
+impl Default for UniformAllocator {
+    fn default() -> Self {
+        Self {
+            pages: vec![],
+            page_size: DEFAULT_UNIFORM_PAGE_SIZE,
+        }
+    }
+}
+
+impl UniformAllocator {
+    pub fn page_size(&self) -> u64 {
+        self.page_size
+    }
+
+    pub fn set_page_size(&mut self, page_size: u64) {
+        self.page_size = align_to(page_size.max(1), UNIFORM_BUFFER_ALIGNMENT);
+    }
+
+    pub fn pages(&self) -> &[IndexHandle<ResourceNode>] {
+        &self.pages
+    }
+
+    pub fn reset(&mut self) {
+        self.pages.clear();
+    }
+}
+
+impl FrameGraph {
+    pub fn uniform_allocator_mut(&mut self) -> &mut UniformAllocator {
+        &mut self.uniform_allocator
+    }
+
+    pub fn allocate_uniform(&mut self, data: &[u8]) -> (ResourceHandle<TransientBuffer>, u64) {
+        let size = data.len() as u64;
+
+        let page = self.uniform_allocator.pages.last().copied().filter(|page| {
+            let resource_node = self.get_resource_node(page);
+            let offset = align_to(
+                resource_node.contents.len() as u64,
+                UNIFORM_BUFFER_ALIGNMENT,
+            );
+
+            offset + size <= resource_node.get_desc::<TransientBuffer>().size
+        });
+
+        let page = match page {
+            Some(page) => page,
+            None => {
+                let page_size = self
+                    .uniform_allocator
+                    .page_size
+                    .max(align_to(size, UNIFORM_BUFFER_ALIGNMENT));
+
+                let name = format!("uniform_page_{}", self.uniform_allocator.pages.len());
+                let desc = TransientBufferDescriptor {
+                    label: Some("uniform_page".to_string()),
+                    size: page_size,
+                    usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                };
+
+                let handle = self.create_resource_node::<TransientBuffer>(
+                    &name,
+                    VirtualResource::Setuped(desc.into()),
+                );
+                self.uniform_allocator.pages.push(handle.raw.index);
+
+                handle.raw.index
+            }
+        };
+
+        let resource_node = self.get_resource_node_mut(&page);
+        let offset = align_to(
+            resource_node.contents.len() as u64,
+            UNIFORM_BUFFER_ALIGNMENT,
+        );
+
+        resource_node.contents.resize(offset as usize, 0);
+        resource_node.contents.extend_from_slice(data);
+
+        let padded_len = align_to(
+            resource_node.contents.len() as u64,
+            wgpu::COPY_BUFFER_ALIGNMENT,
+        );
+        resource_node.contents.resize(padded_len as usize, 0);
+
+        (resource_node.get_handle(), offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FrameGraph, PassNodeBuilderExt, TransientBuffer, UNIFORM_BUFFER_ALIGNMENT};
+
+    #[test]
+    fn allocate_uniform_aligns_offsets_and_spills_into_new_pages() {
+        let mut frame_graph = FrameGraph::default();
+        frame_graph.uniform_allocator_mut().set_page_size(512);
+
+        let (first, second, third) = {
+            let mut builder = frame_graph.create_pass_node_builder("uniforms");
+            (
+                builder.allocate_uniform(&[1; 16]),
+                builder.allocate_uniform(&[2; 64]),
+                builder.allocate_uniform(&[3; 16]),
+            )
+        };
+
+        assert_eq!(first.offset, 0);
+        assert_eq!(second.offset, UNIFORM_BUFFER_ALIGNMENT);
+        assert_eq!(first.buffer.raw, second.buffer.raw);
+
+        assert_eq!(second.dynamic_offset(), Ok(UNIFORM_BUFFER_ALIGNMENT as u32));
+
+        assert_eq!(third.offset, 0);
+        assert_ne!(third.buffer.raw.index, first.buffer.raw.index);
+        assert_eq!(third.get_bind_group_buffer().size.unwrap().get(), 16);
+
+        let first_page = frame_graph.get_resource_node(&first.buffer.raw.index);
+        assert_eq!(first_page.contents.len(), 256 + 64);
+        assert_eq!(first_page.contents[0], 1);
+        assert_eq!(first_page.contents[256], 2);
+        assert_eq!(first_page.get_desc::<TransientBuffer>().size, 512);
+
+        assert_eq!(frame_graph.uniform_allocator_mut().pages().len(), 2);
+    }
+
+    #[test]
+    fn uniform_allocation_rejects_dynamic_offsets_beyond_u32() {
+        let mut frame_graph = FrameGraph::default();
+        let mut allocation = {
+            let mut builder = frame_graph.create_pass_node_builder("uniforms");
+            builder.allocate_uniform(&[0; 16])
+        };
+
+        allocation.offset = u32::MAX as u64 + 1;
+
+        assert!(allocation.dynamic_offset().is_err());
+    }
+}